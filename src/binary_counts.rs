@@ -4,6 +4,7 @@ use std::path::Path;
 
 const MAGIC_HIST: [u8; 4] = *b"IIH1";
 const MAGIC_HEAT: [u8; 4] = *b"IIM1";
+const MAGIC_QUANT: [u8; 4] = *b"IIQ1";
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 /// Header for histogram binary payloads.
@@ -29,6 +30,16 @@ pub struct HeatmapHeader {
     pub height: u32,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Header for quantile sketch payloads.
+pub struct QuantileHeader {
+    pub version: u16,
+    /// Number of values the sketch summarizes.
+    pub population: u32,
+    /// Number of evenly spaced quantiles stored, from the minimum to the maximum.
+    pub points: u32,
+}
+
 /// Writes a histogram payload in the compact `IIH1` little-endian format.
 ///
 /// Fails if `counts.len()` does not match `header.bins`.
@@ -193,11 +204,103 @@ pub fn read_heatmap_bin(path: &Path) -> Result<(HeatmapHeader, Vec<u32>)> {
     ))
 }
 
+/// Encodes a quantile sketch in the compact `IIQ1` little-endian format.
+///
+/// Fails if `quantiles.len()` does not match `header.points`.
+pub fn encode_quantile_sketch(header: QuantileHeader, quantiles: &[f32]) -> Result<Vec<u8>> {
+    if quantiles.len() != header.points as usize {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            "quantiles length does not match header points",
+        ));
+    }
+
+    let mut bytes = Vec::with_capacity(4 + 2 + (2 * 4) + quantiles.len() * 4);
+    bytes.extend_from_slice(&MAGIC_QUANT);
+    bytes.extend_from_slice(&header.version.to_le_bytes());
+    bytes.extend_from_slice(&header.population.to_le_bytes());
+    bytes.extend_from_slice(&header.points.to_le_bytes());
+
+    for value in quantiles {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Decodes and validates a quantile sketch in the `IIQ1` format.
+///
+/// Rejects non-finite or decreasing quantiles, so the result can be handed straight to
+/// [`crate::percentile::SketchPercentiles::from_header`]. Works on in-memory bytes so
+/// clients can query a downloaded sketch without file access.
+pub fn decode_quantile_sketch(bytes: &[u8]) -> Result<(QuantileHeader, Vec<f32>)> {
+    if bytes.len() < 14 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "payload too small to contain quantile header",
+        ));
+    }
+    if bytes[0..4] != MAGIC_QUANT {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "invalid quantile sketch magic header",
+        ));
+    }
+
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    let population = u32::from_le_bytes([bytes[6], bytes[7], bytes[8], bytes[9]]);
+    let points = u32::from_le_bytes([bytes[10], bytes[11], bytes[12], bytes[13]]);
+
+    let payload = &bytes[14..];
+    if payload.len() != points as usize * 4 {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "quantile payload length does not match header points",
+        ));
+    }
+
+    let quantiles: Vec<f32> = payload
+        .chunks_exact(4)
+        .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+    if quantiles.iter().any(|q| !q.is_finite()) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "quantile sketch contains a non-finite value",
+        ));
+    }
+    if quantiles.windows(2).any(|w| w[0] > w[1]) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            "quantile sketch values are not non-decreasing",
+        ));
+    }
+
+    Ok((
+        QuantileHeader {
+            version,
+            population,
+            points,
+        },
+        quantiles,
+    ))
+}
+
+/// Writes a quantile sketch payload in the `IIQ1` format.
+pub fn write_quantile_bin(path: &Path, header: QuantileHeader, quantiles: &[f32]) -> Result<()> {
+    fs::write(path, encode_quantile_sketch(header, quantiles)?)
+}
+
+/// Reads and validates a quantile sketch payload in the `IIQ1` format.
+pub fn read_quantile_bin(path: &Path) -> Result<(QuantileHeader, Vec<f32>)> {
+    decode_quantile_sketch(&fs::read(path)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
-        HeatmapHeader, HistogramHeader, read_heatmap_bin, read_histogram_bin, write_heatmap_bin,
-        write_histogram_bin,
+        HeatmapHeader, HistogramHeader, QuantileHeader, decode_quantile_sketch,
+        encode_quantile_sketch, read_heatmap_bin, read_histogram_bin, read_quantile_bin,
+        write_heatmap_bin, write_histogram_bin, write_quantile_bin,
     };
     #[test]
     fn histogram_roundtrip() {
@@ -270,4 +373,57 @@ mod tests {
         assert!(result.is_err());
         let _ = std::fs::remove_file(path);
     }
+
+    #[test]
+    fn quantile_roundtrip() {
+        let mut path = std::env::temp_dir();
+        path.push("ii_quant_roundtrip.bin");
+
+        let header = QuantileHeader {
+            version: 1,
+            population: 1_000,
+            points: 3,
+        };
+        let quantiles = vec![100.0, 250.0, 400.0];
+
+        write_quantile_bin(&path, header, &quantiles).expect("write should succeed");
+        let (read_header, read_quantiles) = read_quantile_bin(&path).expect("read should succeed");
+
+        assert_eq!(read_header, header);
+        assert_eq!(read_quantiles, quantiles);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn invalid_quantile_payload_is_rejected() {
+        let header = QuantileHeader {
+            version: 1,
+            population: 10,
+            points: 2,
+        };
+        assert!(encode_quantile_sketch(header, &[1.0]).is_err());
+
+        let mut bytes = encode_quantile_sketch(header, &[1.0, 2.0]).unwrap();
+        bytes.pop();
+        assert!(decode_quantile_sketch(&bytes).is_err());
+        assert!(decode_quantile_sketch(b"IIH1").is_err());
+    }
+
+    #[test]
+    fn corrupt_quantile_values_are_rejected() {
+        let header = QuantileHeader {
+            version: 1,
+            population: 10,
+            points: 3,
+        };
+        for quantiles in [
+            [3.0, 2.0, 1.0],
+            [1.0, f32::NAN, 3.0],
+            [1.0, 2.0, f32::INFINITY],
+        ] {
+            let bytes = encode_quantile_sketch(header, &quantiles).unwrap();
+            let err = decode_quantile_sketch(&bytes).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
 }
@@ -0,0 +1,173 @@
+//! Era adjustment: rescales results by how the population median moved over time.
+//!
+//! An adjusted value is `value * reference_median / median(year)`, so a result that sat at
+//! the median in its year maps to the median of the reference year. Medians come from the
+//! published `trends_shards/<sex>/<equip>/trends.json` series.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Years with fewer results than this are too noisy to anchor an adjustment.
+pub const DEFAULT_MIN_YEAR_TOTAL: u32 = 30;
+
+/// One year of a published trend series.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct TrendPoint {
+    pub year: i32,
+    pub total: u32,
+    pub p50: f64,
+    pub p90: f64,
+}
+
+/// Yearly points for one slice key (`sex=M|equip=Raw|tested=All|lift=B|metric=Kg`).
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TrendSeries {
+    pub key: String,
+    pub points: Vec<TrendPoint>,
+}
+
+/// A published `trends.json` shard.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TrendsFile {
+    pub version: String,
+    pub bucket: String,
+    pub series: Vec<TrendSeries>,
+}
+
+impl TrendsFile {
+    pub fn series(&self, key: &str) -> Option<&TrendSeries> {
+        self.series.iter().find(|series| series.key == key)
+    }
+}
+
+/// Per-year medians for one series and the reference median they are scaled to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EraAdjustment {
+    medians: BTreeMap<i32, f64>,
+    reference_year: i32,
+    reference_median: f64,
+}
+
+impl EraAdjustment {
+    /// Builds the adjustment from years with at least `min_total` results.
+    ///
+    /// The reference is `reference_year` if given and usable, otherwise the latest usable
+    /// year. Returns `None` if no year qualifies.
+    pub fn from_series(
+        series: &TrendSeries,
+        reference_year: Option<i32>,
+        min_total: u32,
+    ) -> Option<Self> {
+        let medians: BTreeMap<i32, f64> = series
+            .points
+            .iter()
+            .filter(|point| point.total >= min_total && point.p50 > 0.0)
+            .map(|point| (point.year, point.p50))
+            .collect();
+        let (reference_year, reference_median) = reference_year
+            .and_then(|year| medians.get(&year).map(|median| (year, *median)))
+            .or_else(|| {
+                medians
+                    .last_key_value()
+                    .map(|(year, median)| (*year, *median))
+            })?;
+        Some(Self {
+            medians,
+            reference_year,
+            reference_median,
+        })
+    }
+
+    pub fn reference_year(&self) -> i32 {
+        self.reference_year
+    }
+
+    /// Median used for `year`: its own if usable, otherwise the nearest usable year's
+    /// (the earlier one on a tie).
+    pub fn median_for(&self, year: i32) -> f64 {
+        let before = self.medians.range(..=year).next_back();
+        let after = self.medians.range(year..).next();
+        match (before, after) {
+            (Some((y0, m0)), Some((y1, m1))) => {
+                if year - y0 <= y1 - year {
+                    *m0
+                } else {
+                    *m1
+                }
+            }
+            (Some((_, median)), None) | (None, Some((_, median))) => *median,
+            (None, None) => self.reference_median,
+        }
+    }
+
+    /// Multiplier that maps a `year` result onto the reference era.
+    pub fn factor(&self, year: i32) -> f64 {
+        self.reference_median / self.median_for(year)
+    }
+
+    pub fn adjust(&self, value: f64, year: i32) -> f64 {
+        value * self.factor(year)
+    }
+
+    /// Trend points with `p50` and `p90` rescaled to the reference era.
+    pub fn adjust_points(&self, points: &[TrendPoint]) -> Vec<TrendPoint> {
+        points
+            .iter()
+            .map(|point| TrendPoint {
+                p50: self.adjust(point.p50, point.year),
+                p90: self.adjust(point.p90, point.year),
+                ..*point
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DEFAULT_MIN_YEAR_TOTAL, EraAdjustment, TrendsFile};
+
+    const TRENDS: &str = r#"{"version":"v1","bucket":"year","series":[
+        {"key":"sex=M|equip=Raw|tested=All|lift=T|metric=Kg","points":[
+            {"year":1966,"total":1,"p50":900.0,"p90":900.0},
+            {"year":1980,"total":120,"p50":500.0,"p90":650.0},
+            {"year":2000,"total":400,"p50":550.0,"p90":700.0},
+            {"year":2020,"total":900,"p50":600.0,"p90":760.0}
+        ]}
+    ]}"#;
+
+    fn adjustment(reference_year: Option<i32>) -> EraAdjustment {
+        let trends: TrendsFile = serde_json::from_str(TRENDS).unwrap();
+        let series = trends
+            .series("sex=M|equip=Raw|tested=All|lift=T|metric=Kg")
+            .unwrap();
+        EraAdjustment::from_series(series, reference_year, DEFAULT_MIN_YEAR_TOTAL).unwrap()
+    }
+
+    #[test]
+    fn scales_older_results_to_latest_median() {
+        let era = adjustment(None);
+        assert_eq!(era.reference_year(), 2020);
+        assert_eq!(era.adjust(500.0, 1980), 600.0);
+        assert_eq!(era.adjust(600.0, 2020), 600.0);
+    }
+
+    #[test]
+    fn sparse_and_missing_years_use_nearest_usable_year() {
+        let era = adjustment(Some(2000));
+        // 1966 has a single result, so 1980 anchors it.
+        assert_eq!(era.median_for(1966), 500.0);
+        assert_eq!(era.median_for(1990), 500.0);
+        assert_eq!(era.median_for(1991), 550.0);
+        assert_eq!(era.median_for(2030), 600.0);
+        assert!((era.factor(2020) - 550.0 / 600.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn adjusted_medians_are_flat() {
+        let era = adjustment(None);
+        let trends: TrendsFile = serde_json::from_str(TRENDS).unwrap();
+        let adjusted = era.adjust_points(&trends.series[0].points[1..]);
+        assert!(adjusted.iter().all(|point| point.p50 == 600.0));
+        assert!((adjusted[0].p90 - 780.0).abs() < 1e-9);
+    }
+}
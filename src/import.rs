@@ -0,0 +1,479 @@
+use crate::model::{Equipment, Sex};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const LB_TO_KG: f64 = 0.453_592_37;
+const MAX_BODYWEIGHT_KG: f64 = 300.0;
+const MAX_LIFT_KG: f64 = 1_500.0;
+
+/// Unit used by the weight columns of an imported CSV.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WeightUnit {
+    #[default]
+    Kg,
+    Lb,
+}
+
+impl WeightUnit {
+    fn to_kg(self, value: f64) -> f64 {
+        match self {
+            WeightUnit::Kg => value,
+            WeightUnit::Lb => value * LB_TO_KG,
+        }
+    }
+}
+
+/// Maps arbitrary CSV headers onto the fields Iron Insights needs.
+///
+/// Header matching is case-insensitive. Lift columns are optional; when `total` is not
+/// mapped it is derived from squat + bench + deadlift if all three are present.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnMapping {
+    pub name: String,
+    pub sex: String,
+    pub bodyweight: String,
+    #[serde(default)]
+    pub squat: Option<String>,
+    #[serde(default)]
+    pub bench: Option<String>,
+    #[serde(default)]
+    pub deadlift: Option<String>,
+    #[serde(default)]
+    pub total: Option<String>,
+    /// Optional column holding a DOTS score computed elsewhere, kept for validation.
+    #[serde(default)]
+    pub dots: Option<String>,
+    /// Optional equipment column; any spelling [`Equipment::normalize`] accepts.
+    #[serde(default)]
+    pub equipment: Option<String>,
+    #[serde(default)]
+    pub unit: WeightUnit,
+}
+
+/// One validated row, with every weight converted to kg.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportedLifter {
+    pub name: String,
+    pub sex: Sex,
+    pub bodyweight_kg: f64,
+    pub squat_kg: Option<f64>,
+    pub bench_kg: Option<f64>,
+    pub deadlift_kg: Option<f64>,
+    pub total_kg: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reported_dots: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equipment: Option<Equipment>,
+}
+
+/// A data row that failed validation. `line` is 1-based, counts the header, and is where
+/// the record starts if a quoted field spans several lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RowError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Error that prevents the file from being imported at all.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImportError {
+    EmptyInput,
+    UnterminatedHeader,
+    MissingColumn(String),
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::EmptyInput => f.write_str("CSV input has no header row"),
+            ImportError::UnterminatedHeader => {
+                f.write_str("CSV header has a quoted field that is never closed")
+            }
+            ImportError::MissingColumn(column) => {
+                write!(f, "mapped column {column:?} not found in CSV header")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Accepted rows plus per-row validation failures.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ImportReport {
+    pub lifters: Vec<ImportedLifter>,
+    pub rejected: Vec<RowError>,
+}
+
+/// One CSV record and the 1-based line it starts on.
+struct Record {
+    line: usize,
+    fields: Vec<String>,
+    /// `false` if the input ended inside a quoted field.
+    terminated: bool,
+}
+
+/// Splits CSV input into records, honouring double-quoted fields (which may span lines)
+/// and `""` escapes. Blank lines are skipped.
+fn split_records(input: &str) -> Vec<Record> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    let mut line = 1;
+    let mut start = 1;
+    let mut chars = input.chars().peekable();
+
+    let mut finish = |fields: Vec<String>, quoted: bool, start: usize, terminated: bool| {
+        let blank = !quoted && fields.len() == 1 && fields[0].trim().is_empty();
+        if !blank {
+            records.push(Record {
+                line: start,
+                fields,
+                terminated,
+            });
+        }
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                quoted = true;
+            }
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut field));
+                finish(std::mem::take(&mut fields), quoted, start, true);
+                quoted = false;
+                line += 1;
+                start = line;
+            }
+            '\n' => {
+                field.push(c);
+                line += 1;
+            }
+            _ => field.push(c),
+        }
+    }
+    if quoted || !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        finish(fields, quoted, start, !in_quotes);
+    }
+    records
+}
+
+struct ColumnIndexes {
+    name: usize,
+    sex: usize,
+    bodyweight: usize,
+    squat: Option<usize>,
+    bench: Option<usize>,
+    deadlift: Option<usize>,
+    total: Option<usize>,
+    dots: Option<usize>,
+    equipment: Option<usize>,
+}
+
+fn resolve_columns(
+    header: &[String],
+    mapping: &ColumnMapping,
+) -> Result<ColumnIndexes, ImportError> {
+    let find = |column: &str| {
+        header
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(column.trim()))
+            .ok_or_else(|| ImportError::MissingColumn(column.to_string()))
+    };
+    let find_optional = |column: &Option<String>| column.as_deref().map(find).transpose();
+
+    Ok(ColumnIndexes {
+        name: find(&mapping.name)?,
+        sex: find(&mapping.sex)?,
+        bodyweight: find(&mapping.bodyweight)?,
+        squat: find_optional(&mapping.squat)?,
+        bench: find_optional(&mapping.bench)?,
+        deadlift: find_optional(&mapping.deadlift)?,
+        total: find_optional(&mapping.total)?,
+        dots: find_optional(&mapping.dots)?,
+        equipment: find_optional(&mapping.equipment)?,
+    })
+}
+
+fn parse_weight(
+    fields: &[String],
+    index: Option<usize>,
+    label: &str,
+    unit: WeightUnit,
+    max_kg: f64,
+) -> Result<Option<f64>, String> {
+    let Some(raw) = index.and_then(|i| fields.get(i)).map(|s| s.trim()) else {
+        return Ok(None);
+    };
+    if raw.is_empty() {
+        return Ok(None);
+    }
+    let value: f64 = raw
+        .parse()
+        .map_err(|_| format!("{label} {raw:?} is not a number"))?;
+    let kg = unit.to_kg(value);
+    if !kg.is_finite() || kg <= 0.0 || kg > max_kg {
+        return Err(format!("{label} {raw} is outside the plausible range"));
+    }
+    Ok(Some(kg))
+}
+
+fn parse_row(
+    fields: &[String],
+    columns: &ColumnIndexes,
+    unit: WeightUnit,
+) -> Result<ImportedLifter, String> {
+    let cell = |i: usize| fields.get(i).map(|s| s.trim()).unwrap_or("");
+
+    let name = cell(columns.name);
+    if name.is_empty() {
+        return Err("name is empty".to_string());
+    }
+    let sex: Sex = cell(columns.sex).parse().map_err(|e| format!("{e}"))?;
+    let bodyweight_kg = parse_weight(
+        fields,
+        Some(columns.bodyweight),
+        "bodyweight",
+        unit,
+        MAX_BODYWEIGHT_KG,
+    )?
+    .ok_or_else(|| "bodyweight is empty".to_string())?;
+
+    let squat_kg = parse_weight(fields, columns.squat, "squat", unit, MAX_LIFT_KG)?;
+    let bench_kg = parse_weight(fields, columns.bench, "bench", unit, MAX_LIFT_KG)?;
+    let deadlift_kg = parse_weight(fields, columns.deadlift, "deadlift", unit, MAX_LIFT_KG)?;
+    let total_kg = match parse_weight(fields, columns.total, "total", unit, MAX_LIFT_KG * 3.0)? {
+        Some(total) => Some(total),
+        None => match (squat_kg, bench_kg, deadlift_kg) {
+            (Some(s), Some(b), Some(d)) => Some(s + b + d),
+            _ => None,
+        },
+    };
+
+    let reported_dots = match columns.dots.map(cell).filter(|raw| !raw.is_empty()) {
+        Some(raw) => {
+            let dots: f64 = raw
+                .parse()
+                .map_err(|_| format!("DOTS {raw:?} is not a number"))?;
+            if !dots.is_finite() || dots < 0.0 {
+                return Err(format!("DOTS {raw} is outside the plausible range"));
+            }
+            Some(dots)
+        }
+        None => None,
+    };
+
+    let equipment = match columns.equipment.map(cell).filter(|raw| !raw.is_empty()) {
+        Some(raw) => Some(raw.parse::<Equipment>().map_err(|e| format!("{e}"))?),
+        None => None,
+    };
+
+    Ok(ImportedLifter {
+        name: name.to_string(),
+        sex,
+        bodyweight_kg,
+        squat_kg,
+        bench_kg,
+        deadlift_kg,
+        total_kg,
+        reported_dots,
+        equipment,
+    })
+}
+
+/// Parses a gym CSV using `mapping`, validating every data row.
+///
+/// Fails only when the header is missing or a mapped column does not exist; individual bad
+/// rows are reported in [`ImportReport::rejected`] so the rest of the file still imports.
+pub fn import_csv(input: &str, mapping: &ColumnMapping) -> Result<ImportReport, ImportError> {
+    let mut records = split_records(input.trim_start_matches('\u{feff}')).into_iter();
+    let header = records.next().ok_or(ImportError::EmptyInput)?;
+    if !header.terminated {
+        return Err(ImportError::UnterminatedHeader);
+    }
+    let columns = resolve_columns(&header.fields, mapping)?;
+
+    let mut report = ImportReport::default();
+    for record in records {
+        let row = if record.terminated {
+            parse_row(&record.fields, &columns, mapping.unit)
+        } else {
+            Err("quoted field is never closed".to_string())
+        };
+        match row {
+            Ok(lifter) => report.lifters.push(lifter),
+            Err(message) => report.rejected.push(RowError {
+                line: record.line,
+                message,
+            }),
+        }
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ColumnMapping, ImportError, WeightUnit, import_csv, split_records};
+    use crate::model::{Equipment, Sex};
+
+    fn gym_mapping() -> ColumnMapping {
+        ColumnMapping {
+            name: "Athlete".to_string(),
+            sex: "Gender".to_string(),
+            bodyweight: "BW".to_string(),
+            squat: Some("Squat".to_string()),
+            bench: Some("Bench".to_string()),
+            deadlift: Some("Pull".to_string()),
+            total: None,
+            dots: None,
+            equipment: None,
+            unit: WeightUnit::Kg,
+        }
+    }
+
+    #[test]
+    fn split_records_handles_quotes() {
+        let records = split_records(r#"a,"b, c","say ""hi""""#);
+        assert_eq!(records[0].fields, vec!["a", "b, c", r#"say "hi""#]);
+    }
+
+    #[test]
+    fn quoted_newlines_stay_in_one_record() {
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull\r\n\
+                   \"Doe,\nJane\",F,63.2,140,80,170\r\n\
+                   \r\n\
+                   Sam,M,90,200,140,250\n";
+        let report = import_csv(csv, &gym_mapping()).expect("import should succeed");
+
+        assert!(report.rejected.is_empty(), "{:?}", report.rejected);
+        assert_eq!(report.lifters.len(), 2);
+        assert_eq!(report.lifters[0].name, "Doe,\nJane");
+        assert_eq!(report.lifters[1].name, "Sam");
+    }
+
+    #[test]
+    fn unterminated_quote_is_rejected() {
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull\n\
+                   Sam,M,90,200,140,250\n\
+                   \"Doe, Jane,F,63.2,140,80,170\n\
+                   Kim,F,57,120,70,150\n";
+        let report = import_csv(csv, &gym_mapping()).expect("import should succeed");
+
+        assert_eq!(report.lifters.len(), 1);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].line, 3);
+        assert_eq!(report.rejected[0].message, "quoted field is never closed");
+
+        assert_eq!(
+            import_csv("\"Athlete,Gender\n", &gym_mapping()),
+            Err(ImportError::UnterminatedHeader)
+        );
+    }
+
+    #[test]
+    fn imports_mapped_columns_and_derives_total() {
+        let csv = "Athlete,gender,BW,Squat,Bench,Pull\n\"Doe, Jane\",F,63.2,140,80,170\n";
+        let report = import_csv(csv, &gym_mapping()).expect("import should succeed");
+
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.lifters.len(), 1);
+        let lifter = &report.lifters[0];
+        assert_eq!(lifter.name, "Doe, Jane");
+        assert_eq!(lifter.sex, Sex::Female);
+        assert_eq!(lifter.total_kg, Some(390.0));
+    }
+
+    #[test]
+    fn converts_pounds_to_kilograms() {
+        let mapping = ColumnMapping {
+            unit: WeightUnit::Lb,
+            ..gym_mapping()
+        };
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull\nSam,M,200,405,,\n";
+        let report = import_csv(csv, &mapping).expect("import should succeed");
+        let lifter = &report.lifters[0];
+
+        assert!((lifter.bodyweight_kg - 90.718).abs() < 0.001);
+        assert!((lifter.squat_kg.unwrap() - 183.705).abs() < 0.001);
+        assert_eq!(lifter.bench_kg, None);
+        assert_eq!(lifter.total_kg, None);
+    }
+
+    #[test]
+    fn rejects_bad_rows_but_keeps_good_ones() {
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull\n\
+                   Good,M,93,200,140,250\n\
+                   ,M,93,200,140,250\n\
+                   Alien,X,93,200,140,250\n\
+                   Heavy,M,93,2000,140,250\n";
+        let report = import_csv(csv, &gym_mapping()).expect("import should succeed");
+
+        assert_eq!(report.lifters.len(), 1);
+        let lines: Vec<usize> = report.rejected.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn rejects_non_finite_reported_dots() {
+        let mapping = ColumnMapping {
+            dots: Some("Dots".to_string()),
+            ..gym_mapping()
+        };
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull,Dots\n\
+                   A,M,93,200,140,250,370.5\n\
+                   B,M,93,200,140,250,NaN\n\
+                   C,M,93,200,140,250,inf\n\
+                   D,M,93,200,140,250,-5\n";
+        let report = import_csv(csv, &mapping).expect("import should succeed");
+
+        assert_eq!(report.lifters.len(), 1);
+        assert_eq!(report.lifters[0].reported_dots, Some(370.5));
+        let lines: Vec<usize> = report.rejected.iter().map(|e| e.line).collect();
+        assert_eq!(lines, vec![3, 4, 5]);
+    }
+
+    #[test]
+    fn normalizes_equipment_and_rejects_unknown_values() {
+        let mapping = ColumnMapping {
+            equipment: Some("Kit".to_string()),
+            ..gym_mapping()
+        };
+        let csv = "Athlete,Gender,BW,Squat,Bench,Pull,Kit\n\
+                   A,M,93,200,140,250,Classic\n\
+                   B,M,93,200,140,250,\n\
+                   C,M,93,200,140,250,Bionic\n";
+        let report = import_csv(csv, &mapping).expect("import should succeed");
+
+        assert_eq!(report.lifters[0].equipment, Some(Equipment::Raw));
+        assert_eq!(report.lifters[1].equipment, None);
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.rejected[0].message, "unknown equipment: \"Bionic\"");
+    }
+
+    #[test]
+    fn missing_mapped_column_is_fatal() {
+        let csv = "Athlete,Gender,Bodyweight\nSam,M,90\n";
+        assert_eq!(
+            import_csv(csv, &gym_mapping()),
+            Err(ImportError::MissingColumn("BW".to_string()))
+        );
+    }
+}
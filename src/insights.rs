@@ -0,0 +1,717 @@
+//! High-level entry point for embedding Iron Insights analytics in other applications.
+//!
+//! Works on the lifter records written by the `import` command and has no web or
+//! server dependencies.
+
+use crate::import::ImportedLifter;
+use crate::model::{Equipment, EquipmentFilter, LiftType, Sex, WeightClass};
+use crate::percentile::{
+    CacheStats, DEFAULT_MIN_SAMPLE, PercentileCache, PercentileConfidence, PercentileEngine,
+    PercentileMethod, SortedPercentiles, percentile_confidence,
+};
+use crate::scoring::{Formula, GlEvent};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Error returned when a lifter dataset cannot be loaded.
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "failed to read lifter records: {err}"),
+            LoadError::Json(err) => write!(f, "invalid lifter records JSON: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(err) => Some(err),
+            LoadError::Json(err) => Some(err),
+        }
+    }
+}
+
+/// Which lifters and which number to compare.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Query {
+    /// `None` pools both sexes.
+    pub sex: Option<Sex>,
+    /// `Only` skips lifters whose equipment is different or unknown.
+    pub equipment: EquipmentFilter,
+    /// Only lifters in this IPF class for their sex; `None` pools every class.
+    pub class: Option<WeightClass>,
+    pub lift: LiftType,
+    /// Score the lift with this formula; `None` compares raw kg. An IPF GL formula only
+    /// matches the lift its event scores, so other lifts have no value.
+    pub formula: Option<Formula>,
+}
+
+impl Query {
+    /// Raw-kg query for one sex and lift.
+    pub fn kg(sex: Sex, lift: LiftType) -> Self {
+        Self {
+            sex: Some(sex),
+            equipment: EquipmentFilter::All,
+            class: None,
+            lift,
+            formula: None,
+        }
+    }
+
+    fn value(&self, lifter: &ImportedLifter) -> Option<f64> {
+        if self.sex.is_some_and(|sex| sex != lifter.sex) {
+            return None;
+        }
+        if let EquipmentFilter::Only(only) = self.equipment
+            && lifter.equipment != Some(only)
+        {
+            return None;
+        }
+        if self.class.is_some_and(|class| {
+            class != WeightClass::for_bodyweight(lifter.sex, lifter.bodyweight_kg)
+        }) {
+            return None;
+        }
+        if let Some(Formula::IpfGl { event, .. }) = self.formula
+            && GlEvent::for_lift(self.lift) != Some(event)
+        {
+            return None;
+        }
+        let kg = match self.lift {
+            LiftType::Squat => lifter.squat_kg,
+            LiftType::Bench => lifter.bench_kg,
+            LiftType::Deadlift => lifter.deadlift_kg,
+            LiftType::Total => lifter.total_kg,
+        }?;
+        Some(match self.formula {
+            Some(formula) => formula.score(kg, lifter.bodyweight_kg, lifter.sex),
+            None => kg,
+        })
+    }
+}
+
+/// One row of a leaderboard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Ranking {
+    /// 1-based; tied values share a rank.
+    pub rank: usize,
+    pub name: String,
+    pub sex: Sex,
+    pub bodyweight_kg: f64,
+    pub value: f64,
+}
+
+/// Leaderboard for one sex and weight class.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClassRankings {
+    pub sex: Sex,
+    pub class: WeightClass,
+    pub rankings: Vec<Ranking>,
+}
+
+/// Filter values present in a dataset, so dropdowns can be built from data.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct FilterOptions {
+    pub sexes: Vec<Sex>,
+    /// Weight classes with at least one lifter, per sex, lightest first.
+    pub weight_classes: Vec<(Sex, Vec<WeightClass>)>,
+    /// Equipment recorded for at least one lifter, in [`Equipment::ALL`] order.
+    pub equipment: Vec<Equipment>,
+    /// Lifts with at least one recorded result.
+    pub lifts: Vec<LiftType>,
+    /// Smallest and largest bodyweight in kg.
+    pub bodyweight_range: Option<(f64, f64)>,
+}
+
+/// Percentile gap (points) below which a lifter's profile counts as balanced.
+pub const BALANCED_SPREAD: f64 = 10.0;
+
+/// Per-lift DOTS percentiles for one lifter and the shape they describe.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LiftProfile {
+    /// Squat, bench, deadlift, and total, in [`LiftType::ALL`] order.
+    pub percentiles: Vec<(LiftType, f64)>,
+    /// Lift with the highest percentile.
+    pub strongest: LiftType,
+    /// Lift with the lowest percentile.
+    pub weakest: LiftType,
+    /// Whether the three lifts are within [`BALANCED_SPREAD`] points of each other.
+    pub balanced: bool,
+}
+
+/// An anonymized lifter close to a given bodyweight and result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Neighbor {
+    pub bodyweight_kg: f64,
+    pub value: f64,
+    /// Combined relative distance in bodyweight and value; smaller is closer.
+    pub distance: f64,
+}
+
+/// A loaded lifter dataset with percentile and ranking queries.
+///
+/// Sorted populations are cached per [`Query`], so repeated percentile lookups against the
+/// same filters skip the filter-and-sort pass.
+#[derive(Debug)]
+pub struct IronInsights {
+    lifters: Vec<ImportedLifter>,
+    populations: Mutex<PercentileCache<Query>>,
+}
+
+impl Default for IronInsights {
+    fn default() -> Self {
+        Self::from_lifters(Vec::new())
+    }
+}
+
+impl Clone for IronInsights {
+    fn clone(&self) -> Self {
+        Self::from_lifters(self.lifters.clone())
+    }
+}
+
+impl PartialEq for IronInsights {
+    fn eq(&self, other: &Self) -> bool {
+        self.lifters == other.lifters
+    }
+}
+
+impl IronInsights {
+    /// Loads lifter records written by `iron_insights import`.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let bytes = fs::read(path).map_err(LoadError::Io)?;
+        let lifters = serde_json::from_slice(&bytes).map_err(LoadError::Json)?;
+        Ok(Self::from_lifters(lifters))
+    }
+
+    pub fn from_lifters(lifters: Vec<ImportedLifter>) -> Self {
+        Self {
+            lifters,
+            populations: Mutex::new(PercentileCache::new("")),
+        }
+    }
+
+    pub fn lifters(&self) -> &[ImportedLifter] {
+        &self.lifters
+    }
+
+    /// Percentile engine over every lifter matching `query`, built once per query.
+    pub fn population(&self, query: &Query) -> Arc<SortedPercentiles> {
+        self.populations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_build(*query, || {
+                self.lifters
+                    .iter()
+                    .filter_map(|lifter| query.value(lifter))
+                    .collect()
+            })
+    }
+
+    /// Hit and miss counts for the per-query population cache.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.populations
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .stats()
+    }
+
+    /// Interpolated percentile (0-100, one decimal) of `value` among lifters matching `query`.
+    pub fn percentile(&self, query: &Query, value: f64) -> Option<f64> {
+        self.population(query)
+            .display_percentile(value, PercentileMethod::Interpolated)
+    }
+
+    /// [`IronInsights::percentile`] plus how far it can be trusted given the number of
+    /// lifters matching `query`.
+    pub fn percentile_with_confidence(
+        &self,
+        query: &Query,
+        value: f64,
+    ) -> Option<(f64, PercentileConfidence)> {
+        let engine = self.population(query);
+        let percentile = engine.display_percentile(value, PercentileMethod::Interpolated)?;
+        let confidence = percentile_confidence(percentile, engine.population(), DEFAULT_MIN_SAMPLE);
+        Some((percentile, confidence))
+    }
+
+    /// Top `limit` lifters matching `query`, best first.
+    pub fn rankings(&self, query: &Query, limit: usize) -> Vec<Ranking> {
+        rank(self.lifters.iter(), query, limit)
+    }
+
+    /// Distinct sexes, weight classes, equipment, and lifts present in the dataset.
+    pub fn filter_options(&self) -> FilterOptions {
+        let mut options = FilterOptions::default();
+        for sex in Sex::ALL {
+            let mut classes: Vec<WeightClass> = self
+                .lifters
+                .iter()
+                .filter(|lifter| lifter.sex == sex)
+                .map(|lifter| WeightClass::for_bodyweight(sex, lifter.bodyweight_kg))
+                .collect();
+            if classes.is_empty() {
+                continue;
+            }
+            classes.sort();
+            classes.dedup();
+            options.sexes.push(sex);
+            options.weight_classes.push((sex, classes));
+        }
+        options.equipment = Equipment::ALL
+            .into_iter()
+            .filter(|&equipment| {
+                self.lifters
+                    .iter()
+                    .any(|lifter| lifter.equipment == Some(equipment))
+            })
+            .collect();
+        options.lifts = LiftType::ALL
+            .into_iter()
+            .filter(|&lift| {
+                self.lifters
+                    .iter()
+                    .any(|lifter| Query::kg(lifter.sex, lift).value(lifter).is_some())
+            })
+            .collect();
+        options.bodyweight_range =
+            self.lifters
+                .iter()
+                .map(|lifter| lifter.bodyweight_kg)
+                .fold(None, |range, bw| match range {
+                    None => Some((bw, bw)),
+                    Some((lo, hi)) => Some((f64::min(lo, bw), f64::max(hi, bw))),
+                });
+        options
+    }
+
+    /// Top `limit` lifters matching `query` within each sex and IPF weight class.
+    ///
+    /// Groups are ordered by sex, then class from lightest to heaviest.
+    pub fn class_rankings(&self, query: &Query, limit: usize) -> Vec<ClassRankings> {
+        let mut groups: Vec<(Sex, WeightClass, Vec<&ImportedLifter>)> = Vec::new();
+        for lifter in &self.lifters {
+            let class = WeightClass::for_bodyweight(lifter.sex, lifter.bodyweight_kg);
+            match groups
+                .iter_mut()
+                .find(|(sex, c, _)| *sex == lifter.sex && *c == class)
+            {
+                Some((_, _, members)) => members.push(lifter),
+                None => groups.push((lifter.sex, class, vec![lifter])),
+            }
+        }
+        groups.sort_by_key(|(sex, class, _)| (sex.code(), *class));
+
+        groups
+            .into_iter()
+            .map(|(sex, class, members)| ClassRankings {
+                sex,
+                class,
+                rankings: rank(members.into_iter(), query, limit),
+            })
+            .filter(|group| !group.rankings.is_empty())
+            .collect()
+    }
+
+    /// Percentile of each lift and the total among same-sex lifters matching `equipment`,
+    /// scored with DOTS so bodyweight is accounted for, plus which lift is strongest and
+    /// weakest.
+    ///
+    /// Returns `None` if any lift has no comparison population.
+    pub fn lift_profile(
+        &self,
+        sex: Sex,
+        equipment: EquipmentFilter,
+        bodyweight_kg: f64,
+        [squat, bench, deadlift]: [f64; 3],
+    ) -> Option<LiftProfile> {
+        let total = squat + bench + deadlift;
+        let mut percentiles = Vec::with_capacity(LiftType::ALL.len());
+        for (lift, kg) in LiftType::ALL
+            .into_iter()
+            .zip([squat, bench, deadlift, total])
+        {
+            let query = Query {
+                equipment,
+                formula: Some(Formula::Dots),
+                ..Query::kg(sex, lift)
+            };
+            let score = Formula::Dots.score(kg, bodyweight_kg, sex);
+            percentiles.push((lift, self.percentile(&query, score)?));
+        }
+
+        let lifts = &percentiles[..3];
+        let by_percentile = |a: &&(LiftType, f64), b: &&(LiftType, f64)| a.1.total_cmp(&b.1);
+        let (strongest, high) = *lifts.iter().max_by(by_percentile)?;
+        let (weakest, low) = *lifts.iter().min_by(by_percentile)?;
+        Some(LiftProfile {
+            percentiles,
+            strongest,
+            weakest,
+            balanced: high - low < BALANCED_SPREAD,
+        })
+    }
+
+    /// The `n` lifters matching `query` closest to `bodyweight_kg` and `value`, without names.
+    ///
+    /// Distance is the Euclidean norm of the relative bodyweight and value differences, so
+    /// a 5% gap in either counts the same.
+    pub fn neighbors(
+        &self,
+        query: &Query,
+        bodyweight_kg: f64,
+        value: f64,
+        n: usize,
+    ) -> Vec<Neighbor> {
+        if !(bodyweight_kg > 0.0 && value > 0.0) {
+            return Vec::new();
+        }
+        let mut neighbors: Vec<Neighbor> = self
+            .lifters
+            .iter()
+            .filter_map(|lifter| {
+                let other = query.value(lifter)?;
+                let dw = (lifter.bodyweight_kg - bodyweight_kg) / bodyweight_kg;
+                let dv = (other - value) / value;
+                Some(Neighbor {
+                    bodyweight_kg: lifter.bodyweight_kg,
+                    value: other,
+                    distance: dw.hypot(dv),
+                })
+            })
+            .filter(|neighbor| neighbor.distance.is_finite())
+            .collect();
+        neighbors.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+        neighbors.truncate(n);
+        neighbors
+    }
+}
+
+fn rank<'a>(
+    lifters: impl Iterator<Item = &'a ImportedLifter>,
+    query: &Query,
+    limit: usize,
+) -> Vec<Ranking> {
+    let mut scored: Vec<(&ImportedLifter, f64)> = lifters
+        .filter_map(|lifter| query.value(lifter).map(|value| (lifter, value)))
+        .filter(|(_, value)| value.is_finite())
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.name.cmp(&b.0.name)));
+
+    let mut rankings: Vec<Ranking> = Vec::with_capacity(limit.min(scored.len()));
+    for (index, (lifter, value)) in scored.into_iter().take(limit).enumerate() {
+        let rank = match rankings.last() {
+            Some(previous) if previous.value == value => previous.rank,
+            _ => index + 1,
+        };
+        rankings.push(Ranking {
+            rank,
+            name: lifter.name.clone(),
+            sex: lifter.sex,
+            bodyweight_kg: lifter.bodyweight_kg,
+            value,
+        });
+    }
+    rankings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IronInsights, Query};
+    use crate::import::ImportedLifter;
+    use crate::model::{Equipment, EquipmentFilter, LiftType, Sex, WeightClass};
+    use crate::scoring::{Formula, GlEvent};
+    use std::sync::Arc;
+
+    fn lifter(name: &str, sex: Sex, bodyweight_kg: f64, total_kg: f64) -> ImportedLifter {
+        ImportedLifter {
+            name: name.to_string(),
+            sex,
+            bodyweight_kg,
+            squat_kg: None,
+            bench_kg: None,
+            deadlift_kg: None,
+            total_kg: Some(total_kg),
+            reported_dots: None,
+            equipment: None,
+        }
+    }
+
+    fn sample() -> IronInsights {
+        IronInsights::from_lifters(vec![
+            lifter("Light", Sex::Male, 66.0, 600.0),
+            lifter("Heavy", Sex::Male, 120.0, 800.0),
+            lifter("Mid", Sex::Male, 93.0, 700.0),
+            lifter("Tied", Sex::Male, 93.0, 700.0),
+            lifter("Jane", Sex::Female, 63.0, 400.0),
+        ])
+    }
+
+    #[test]
+    fn percentile_filters_by_sex() {
+        let insights = sample();
+        let query = Query::kg(Sex::Male, LiftType::Total);
+
+        assert_eq!(insights.population(&query).values().len(), 4);
+        assert_eq!(insights.percentile(&query, 700.0), Some(50.0));
+        assert_eq!(
+            insights.percentile(&Query::kg(Sex::Female, LiftType::Bench), 100.0),
+            None
+        );
+    }
+
+    #[test]
+    fn equipment_filter_skips_other_and_unknown_equipment() {
+        let mut lifters = sample().lifters;
+        lifters[0].equipment = Some(Equipment::Raw);
+        lifters[1].equipment = Some(Equipment::SinglePly);
+        let insights = IronInsights::from_lifters(lifters);
+
+        let raw = Query {
+            equipment: EquipmentFilter::Only(Equipment::Raw),
+            ..Query::kg(Sex::Male, LiftType::Total)
+        };
+        assert_eq!(insights.population(&raw).values(), &[600.0]);
+        let any = Query {
+            equipment: "Any".parse().unwrap(),
+            ..raw
+        };
+        assert_eq!(insights.population(&any).values().len(), 4);
+    }
+
+    #[test]
+    fn percentile_confidence_flags_small_populations() {
+        let insights = sample();
+        let (percentile, confidence) = insights
+            .percentile_with_confidence(&Query::kg(Sex::Male, LiftType::Total), 700.0)
+            .unwrap();
+        assert_eq!(percentile, 50.0);
+        assert_eq!(confidence.population, 4);
+        assert!(confidence.small_sample);
+        assert!(!confidence.is_reliable());
+    }
+
+    #[test]
+    fn ipf_gl_only_scores_the_lift_its_event_covers() {
+        let mut lifters = sample().lifters;
+        lifters[0].bench_kg = Some(150.0);
+        let insights = IronInsights::from_lifters(lifters);
+        let gl = |lift, event| Query {
+            formula: Some(Formula::IpfGl {
+                equipment: Equipment::Raw,
+                event,
+            }),
+            ..Query::kg(Sex::Male, lift)
+        };
+
+        assert_eq!(
+            insights
+                .population(&gl(LiftType::Total, GlEvent::Total))
+                .values()
+                .len(),
+            4
+        );
+        assert_eq!(
+            insights
+                .population(&gl(LiftType::Bench, GlEvent::Bench))
+                .values()
+                .len(),
+            1
+        );
+        assert!(
+            insights
+                .population(&gl(LiftType::Bench, GlEvent::Total))
+                .values()
+                .is_empty()
+        );
+        assert!(
+            insights
+                .population(&gl(LiftType::Total, GlEvent::Bench))
+                .values()
+                .is_empty()
+        );
+        assert!(
+            insights
+                .population(&gl(LiftType::Squat, GlEvent::Total))
+                .values()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn repeated_queries_reuse_the_cached_population() {
+        let insights = sample();
+        let query = Query {
+            formula: Some(Formula::Dots),
+            ..Query::kg(Sex::Male, LiftType::Total)
+        };
+        let first = insights.population(&query);
+        insights.percentile(&query, 500.0);
+        insights.percentile_with_confidence(&query, 500.0);
+        assert!(Arc::ptr_eq(&first, &insights.population(&query)));
+        insights.population(&Query::kg(Sex::Male, LiftType::Total));
+
+        let stats = insights.cache_stats();
+        assert_eq!((stats.hits, stats.misses), (3, 2));
+    }
+
+    #[test]
+    fn rankings_use_formula_and_share_ties() {
+        let insights = sample();
+        let query = Query {
+            sex: None,
+            formula: Some(Formula::Dots),
+            ..Query::kg(Sex::Male, LiftType::Total)
+        };
+        let rankings = insights.rankings(&query, 10);
+
+        assert_eq!(rankings.len(), 5);
+        assert_eq!(rankings[0].name, "Light");
+        let tied: Vec<usize> = rankings
+            .iter()
+            .filter(|r| r.name == "Mid" || r.name == "Tied")
+            .map(|r| r.rank)
+            .collect();
+        assert_eq!(tied[0], tied[1]);
+        assert_eq!(insights.rankings(&query, 2).len(), 2);
+    }
+
+    #[test]
+    fn filter_options_reflect_dataset() {
+        let options = sample().filter_options();
+
+        assert_eq!(options.sexes, vec![Sex::Male, Sex::Female]);
+        assert_eq!(
+            options.weight_classes[1],
+            (Sex::Female, vec![WeightClass::UpTo(63)])
+        );
+        assert_eq!(options.weight_classes[0].1.len(), 3);
+        assert!(options.equipment.is_empty());
+        assert_eq!(options.lifts, vec![LiftType::Total]);
+        assert_eq!(options.bodyweight_range, Some((63.0, 120.0)));
+        assert_eq!(
+            IronInsights::default().filter_options().bodyweight_range,
+            None
+        );
+    }
+
+    #[test]
+    fn filter_options_list_recorded_equipment_in_canonical_order() {
+        let mut lifters = sample().lifters;
+        lifters[0].equipment = Some(Equipment::Wraps);
+        lifters[1].equipment = Some(Equipment::Raw);
+        lifters[2].equipment = Some(Equipment::Wraps);
+        let options = IronInsights::from_lifters(lifters).filter_options();
+
+        assert_eq!(options.equipment, vec![Equipment::Raw, Equipment::Wraps]);
+    }
+
+    #[test]
+    fn class_query_ranks_a_single_class() {
+        let insights = sample();
+        let query = Query {
+            class: Some(WeightClass::UpTo(120)),
+            ..Query::kg(Sex::Male, LiftType::Total)
+        };
+
+        let rankings = insights.rankings(&query, 10);
+        let names: Vec<&str> = rankings.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["Heavy"]);
+        assert_eq!(insights.population(&query).values(), &[800.0]);
+        let empty = Query {
+            class: Some(WeightClass::UpTo(53)),
+            ..query
+        };
+        assert!(insights.rankings(&empty, 10).is_empty());
+    }
+
+    #[test]
+    fn class_rankings_rank_within_each_class() {
+        let insights = sample();
+        let groups = insights.class_rankings(&Query::kg(Sex::Male, LiftType::Total), 10);
+
+        let classes: Vec<WeightClass> = groups.iter().map(|g| g.class).collect();
+        assert_eq!(
+            classes,
+            vec![
+                WeightClass::UpTo(66),
+                WeightClass::UpTo(93),
+                WeightClass::UpTo(120)
+            ]
+        );
+        assert_eq!(groups[0].rankings[0].name, "Light");
+        assert_eq!(groups[0].rankings[0].rank, 1);
+        assert_eq!(groups[1].rankings.len(), 2);
+        assert!(groups[1].rankings.iter().all(|r| r.rank == 1));
+    }
+
+    #[test]
+    fn lift_profile_finds_strongest_and_weakest_lift() {
+        let mut lifters = Vec::new();
+        for i in 0..20 {
+            let kg = 100.0 + i as f64 * 10.0;
+            lifters.push(ImportedLifter {
+                squat_kg: Some(kg),
+                bench_kg: Some(kg * 0.7),
+                deadlift_kg: Some(kg * 1.2),
+                ..lifter(&format!("L{i}"), Sex::Male, 93.0, kg * 2.9)
+            });
+        }
+        let insights = IronInsights::from_lifters(lifters);
+
+        let profile = insights
+            .lift_profile(Sex::Male, EquipmentFilter::All, 93.0, [290.0, 100.0, 280.0])
+            .unwrap();
+        assert_eq!(profile.percentiles.len(), 4);
+        assert_eq!(profile.strongest, LiftType::Squat);
+        assert_eq!(profile.weakest, LiftType::Bench);
+        assert!(!profile.balanced);
+
+        let even = insights
+            .lift_profile(Sex::Male, EquipmentFilter::All, 93.0, [200.0, 140.0, 240.0])
+            .unwrap();
+        assert!(even.balanced);
+        assert!(
+            insights
+                .lift_profile(
+                    Sex::Female,
+                    EquipmentFilter::All,
+                    63.0,
+                    [100.0, 60.0, 120.0]
+                )
+                .is_none()
+        );
+        let raw = EquipmentFilter::Only(Equipment::Raw);
+        assert!(
+            insights
+                .lift_profile(Sex::Male, raw, 93.0, [200.0, 140.0, 240.0])
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn neighbors_are_closest_first_and_same_sex() {
+        let insights = sample();
+        let neighbors = insights.neighbors(&Query::kg(Sex::Male, LiftType::Total), 95.0, 710.0, 3);
+
+        assert_eq!(neighbors.len(), 3);
+        assert_eq!(neighbors[0].value, 700.0);
+        assert!(neighbors.windows(2).all(|w| w[0].distance <= w[1].distance));
+        assert!(neighbors.iter().all(|n| n.value != 400.0));
+        assert!(
+            insights
+                .neighbors(&Query::kg(Sex::Male, LiftType::Total), 0.0, 700.0, 3)
+                .is_empty()
+        );
+    }
+}
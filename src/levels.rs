@@ -0,0 +1,245 @@
+//! Strength levels: named, coloured percentile bands.
+//!
+//! Ladders are plain JSON so deployments can swap in their own names, colours, and
+//! number of tiers.
+
+use crate::model::{LiftType, Sex};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// One rung of a ladder, reached at `min_percentile` (0-100).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Level {
+    pub name: String,
+    /// CSS colour used for badges and chart bands.
+    pub color: String,
+    pub min_percentile: f64,
+}
+
+/// Replacement cutoffs for one sex and/or lift, one per level in ladder order.
+///
+/// A field left unset matches every value; the most specific matching override wins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CutoffOverride {
+    #[serde(default)]
+    pub sex: Option<Sex>,
+    #[serde(default)]
+    pub lift: Option<LiftType>,
+    pub cutoffs: Vec<f64>,
+}
+
+impl CutoffOverride {
+    fn specificity(&self, sex: Sex, lift: LiftType) -> Option<u8> {
+        if self.sex.is_some_and(|s| s != sex) || self.lift.is_some_and(|l| l != lift) {
+            return None;
+        }
+        Some(u8::from(self.sex.is_some()) + u8::from(self.lift.is_some()))
+    }
+}
+
+/// Problem with a ladder definition.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LevelConfigError {
+    Empty,
+    FirstLevelNotZero(f64),
+    OutOfRange(f64),
+    NotAscending { name: String },
+    CutoffCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for LevelConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelConfigError::Empty => f.write_str("level ladder has no levels"),
+            LevelConfigError::FirstLevelNotZero(min) => {
+                write!(f, "first level must start at percentile 0, not {min}")
+            }
+            LevelConfigError::OutOfRange(min) => {
+                write!(f, "level cutoff {min} is outside 0-100")
+            }
+            LevelConfigError::NotAscending { name } => {
+                write!(f, "level {name:?} does not start above the previous level")
+            }
+            LevelConfigError::CutoffCount { expected, found } => {
+                write!(
+                    f,
+                    "cutoff override has {found} cutoffs, expected {expected}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for LevelConfigError {}
+
+/// Ordered levels, lowest first, with optional per-sex / per-lift cutoffs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelLadder {
+    pub levels: Vec<Level>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<CutoffOverride>,
+}
+
+impl Default for LevelLadder {
+    /// Six tiers split at the 20th, 40th, 60th, 80th, and 95th percentiles.
+    fn default() -> Self {
+        let level = |name: &str, color: &str, min_percentile: f64| Level {
+            name: name.to_string(),
+            color: color.to_string(),
+            min_percentile,
+        };
+        Self {
+            levels: vec![
+                level("Beginner", "#9ca3af", 0.0),
+                level("Novice", "#60a5fa", 20.0),
+                level("Intermediate", "#34d399", 40.0),
+                level("Advanced", "#fbbf24", 60.0),
+                level("Elite", "#f97316", 80.0),
+                level("World class", "#ef4444", 95.0),
+            ],
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl LevelLadder {
+    /// Checks that the ladder and every override start at 0 and strictly increase within 0-100.
+    pub fn validate(&self) -> Result<(), LevelConfigError> {
+        let cutoffs: Vec<f64> = self.levels.iter().map(|l| l.min_percentile).collect();
+        self.validate_cutoffs(&cutoffs)?;
+        for over in &self.overrides {
+            if over.cutoffs.len() != self.levels.len() {
+                return Err(LevelConfigError::CutoffCount {
+                    expected: self.levels.len(),
+                    found: over.cutoffs.len(),
+                });
+            }
+            self.validate_cutoffs(&over.cutoffs)?;
+        }
+        Ok(())
+    }
+
+    fn validate_cutoffs(&self, cutoffs: &[f64]) -> Result<(), LevelConfigError> {
+        let first = *cutoffs.first().ok_or(LevelConfigError::Empty)?;
+        if first != 0.0 {
+            return Err(LevelConfigError::FirstLevelNotZero(first));
+        }
+        if let Some(&min) = cutoffs.iter().find(|min| !(0.0..=100.0).contains(*min)) {
+            return Err(LevelConfigError::OutOfRange(min));
+        }
+        if let Some(i) = (1..cutoffs.len()).find(|&i| cutoffs[i] <= cutoffs[i - 1]) {
+            return Err(LevelConfigError::NotAscending {
+                name: self.levels[i].name.clone(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Highest level whose cutoff `percentile` reaches. Assumes a validated ladder.
+    pub fn level_for(&self, percentile: f64) -> Option<&Level> {
+        self.levels
+            .iter()
+            .rev()
+            .find(|level| percentile >= level.min_percentile)
+    }
+
+    /// Like [`LevelLadder::level_for`], but using the most specific override for `sex` and `lift`.
+    pub fn level_for_lift(&self, percentile: f64, sex: Sex, lift: LiftType) -> Option<&Level> {
+        let Some(over) = self
+            .overrides
+            .iter()
+            .filter_map(|over| over.specificity(sex, lift).map(|rank| (rank, over)))
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, over)| over)
+        else {
+            return self.level_for(percentile);
+        };
+        self.levels
+            .iter()
+            .zip(&over.cutoffs)
+            .rev()
+            .find(|(_, min)| percentile >= **min)
+            .map(|(level, _)| level)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CutoffOverride, LevelConfigError, LevelLadder};
+    use crate::model::{LiftType, Sex};
+
+    #[test]
+    fn default_ladder_is_valid_and_classifies_cutoffs() {
+        let ladder = LevelLadder::default();
+        assert_eq!(ladder.validate(), Ok(()));
+        assert_eq!(ladder.level_for(0.0).unwrap().name, "Beginner");
+        assert_eq!(ladder.level_for(19.9).unwrap().name, "Beginner");
+        assert_eq!(ladder.level_for(20.0).unwrap().name, "Novice");
+        assert_eq!(ladder.level_for(99.0).unwrap().name, "World class");
+    }
+
+    #[test]
+    fn custom_ladder_loads_from_json() {
+        let json = r##"{"levels": [
+            {"name": "Untrained", "color": "#ccc", "min_percentile": 0},
+            {"name": "Freak", "color": "#f0f", "min_percentile": 99.5}
+        ]}"##;
+        let ladder: LevelLadder = serde_json::from_str(json).unwrap();
+        assert_eq!(ladder.validate(), Ok(()));
+        assert_eq!(ladder.level_for(99.7).unwrap().name, "Freak");
+    }
+
+    #[test]
+    fn most_specific_override_wins() {
+        let ladder = LevelLadder {
+            overrides: vec![
+                CutoffOverride {
+                    sex: None,
+                    lift: Some(LiftType::Bench),
+                    cutoffs: vec![0.0, 10.0, 30.0, 50.0, 70.0, 90.0],
+                },
+                CutoffOverride {
+                    sex: Some(Sex::Female),
+                    lift: Some(LiftType::Bench),
+                    cutoffs: vec![0.0, 25.0, 45.0, 65.0, 85.0, 97.0],
+                },
+            ],
+            ..LevelLadder::default()
+        };
+        assert_eq!(ladder.validate(), Ok(()));
+
+        let name = |p, sex, lift| ladder.level_for_lift(p, sex, lift).unwrap().name.as_str();
+        assert_eq!(name(92.0, Sex::Male, LiftType::Bench), "World class");
+        assert_eq!(name(92.0, Sex::Female, LiftType::Bench), "Elite");
+        assert_eq!(name(92.0, Sex::Female, LiftType::Squat), "Elite");
+        assert_eq!(name(22.0, Sex::Female, LiftType::Bench), "Beginner");
+        assert_eq!(name(22.0, Sex::Male, LiftType::Squat), "Novice");
+    }
+
+    #[test]
+    fn rejects_malformed_ladders() {
+        let mut ladder = LevelLadder::default();
+        ladder.levels.swap(1, 2);
+        assert!(matches!(
+            ladder.validate(),
+            Err(LevelConfigError::NotAscending { .. })
+        ));
+
+        let mut short = LevelLadder::default();
+        short.overrides.push(CutoffOverride {
+            sex: Some(Sex::Male),
+            lift: None,
+            cutoffs: vec![0.0, 50.0],
+        });
+        assert_eq!(
+            short.validate(),
+            Err(LevelConfigError::CutoffCount {
+                expected: 6,
+                found: 2
+            })
+        );
+
+        ladder.levels.clear();
+        assert_eq!(ladder.validate(), Err(LevelConfigError::Empty));
+    }
+}
@@ -1,2 +1,13 @@
 pub mod binary_counts;
+pub mod era;
+pub mod import;
+pub mod insights;
+pub mod levels;
+pub mod milestones;
+pub mod model;
+pub mod percentile;
 pub mod rebin;
+pub mod scoring;
+pub mod synthetic;
+pub mod tdigest;
+pub mod validate;
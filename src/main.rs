@@ -1,3 +1,442 @@
-fn main() {
-    println!("Hello, world!");
+use anyhow::{Context, Result, bail};
+use clap::{Args, Parser, Subcommand};
+use iron_insights::import::{ColumnMapping, ImportReport, import_csv};
+use iron_insights::insights::{IronInsights, Query};
+use iron_insights::levels::LevelLadder;
+use iron_insights::milestones::{bodyweight_multiples, total_milestones};
+use iron_insights::model::{Equipment, EquipmentFilter, LiftType, Sex, WeightClass};
+use iron_insights::scoring::{Formula, GlEvent, dots, ipf_gl, wilks};
+use iron_insights::synthetic::{GeneratorConfig, generate};
+use iron_insights::validate::validate_import;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Parser)]
+#[command(name = "iron_insights", about = "Iron Insights command-line tools")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Convert a gym CSV into normalized lifter records using a column mapping.
+    Import {
+        /// CSV file exported from a gym or meet spreadsheet.
+        #[arg(long)]
+        input: PathBuf,
+        /// JSON file describing which CSV columns hold name, sex, bodyweight, and lifts.
+        #[arg(long)]
+        mapping: PathBuf,
+        /// Where to write the normalized JSON records.
+        #[arg(long)]
+        out: PathBuf,
+    },
+    /// Check a CSV dataset for rejected rows, null rates, duplicates, and DOTS mismatches.
+    Validate {
+        /// CSV file to check.
+        input: PathBuf,
+        /// JSON column mapping, as used by `import`.
+        #[arg(long)]
+        mapping: PathBuf,
+    },
+    /// Print DOTS, Wilks, and IPF GL for one lifter's total.
+    Score(ScoreArgs),
+    /// Write seeded synthetic lifter records in the same format as `import`.
+    GenData {
+        /// Number of lifters to generate.
+        #[arg(long, default_value_t = 1_000)]
+        count: usize,
+        /// Seed; the same seed and options always produce the same file.
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+        /// Fraction of lifters that are female, 0-1.
+        #[arg(long, default_value_t = 0.3)]
+        female_share: f64,
+        /// Relative equipment weights such as `Raw=0.8,Single-ply=0.2`; unlisted divisions
+        /// get none. Defaults to mostly raw.
+        #[arg(long, value_delimiter = ',', value_parser = parse_equipment_weight)]
+        equipment_weights: Vec<(Equipment, f64)>,
+        #[arg(long)]
+        out: PathBuf,
+    },
+}
+
+#[derive(Debug, Args)]
+struct ScoreArgs {
+    /// Bodyweight in kg.
+    #[arg(long)]
+    bw: f64,
+    /// M or F.
+    #[arg(long)]
+    sex: Sex,
+    #[arg(long)]
+    squat: f64,
+    #[arg(long)]
+    bench: f64,
+    #[arg(long)]
+    deadlift: f64,
+    /// Equipment class used for IPF GL.
+    #[arg(long, default_value = "Raw")]
+    equipment: Equipment,
+    /// Equipment of the dataset lifters to compare against, or `All` to pool every
+    /// division (including records without equipment).
+    #[arg(long, default_value = "All", requires = "dataset")]
+    compare_equipment: EquipmentFilter,
+    /// Lifter records written by `import`, used to rank the DOTS score.
+    #[arg(long)]
+    dataset: Option<PathBuf>,
+    /// JSON level ladder used to name the percentile; defaults to the built-in six tiers.
+    #[arg(long, requires = "dataset")]
+    levels: Option<PathBuf>,
+}
+
+impl ScoreArgs {
+    /// Rejects a non-positive or non-finite bodyweight and negative or non-finite lifts.
+    fn check(&self) -> Result<()> {
+        if !self.bw.is_finite() || self.bw <= 0.0 {
+            bail!("bodyweight must be a positive number of kg");
+        }
+        for (name, kg) in [
+            ("squat", self.squat),
+            ("bench", self.bench),
+            ("deadlift", self.deadlift),
+        ] {
+            if !kg.is_finite() || kg < 0.0 {
+                bail!("{name} must be a non-negative number of kg");
+            }
+        }
+        Ok(())
+    }
+}
+
+fn load_csv(input: &PathBuf, mapping: &PathBuf) -> Result<ImportReport> {
+    let mapping: ColumnMapping = serde_json::from_str(
+        &fs::read_to_string(mapping)
+            .with_context(|| format!("failed to read mapping {}", mapping.display()))?,
+    )
+    .context("invalid column mapping JSON")?;
+    let csv = fs::read_to_string(input)
+        .with_context(|| format!("failed to read CSV {}", input.display()))?;
+    Ok(import_csv(&csv, &mapping)?)
+}
+
+fn run_import(input: PathBuf, mapping: PathBuf, out: PathBuf) -> Result<()> {
+    let report = load_csv(&input, &mapping)?;
+    for rejected in &report.rejected {
+        eprintln!("rejected {rejected}");
+    }
+
+    fs::write(&out, serde_json::to_vec_pretty(&report.lifters)?)
+        .with_context(|| format!("failed to write {}", out.display()))?;
+    println!(
+        "imported {} lifters into {}",
+        report.lifters.len(),
+        out.display()
+    );
+
+    if !report.rejected.is_empty() {
+        bail!("{} rows failed validation", report.rejected.len());
+    }
+    Ok(())
+}
+
+fn run_validate(input: PathBuf, mapping: PathBuf) -> Result<()> {
+    let report = validate_import(&load_csv(&input, &mapping)?);
+    println!("{report}");
+    if !report.is_ok() {
+        bail!("{} failed validation", input.display());
+    }
+    Ok(())
+}
+
+fn load_levels(path: Option<&Path>) -> Result<LevelLadder> {
+    let Some(path) = path else {
+        return Ok(LevelLadder::default());
+    };
+    let ladder: LevelLadder = serde_json::from_slice(
+        &fs::read(path).with_context(|| format!("failed to read levels {}", path.display()))?,
+    )
+    .context("invalid level ladder JSON")?;
+    ladder.validate()?;
+    Ok(ladder)
+}
+
+fn run_score(args: ScoreArgs) -> Result<()> {
+    args.check()?;
+    let total = args.squat + args.bench + args.deadlift;
+    let dots_score = dots(total, args.bw, args.sex);
+    println!("total: {total:.1} kg @ {:.1} kg {}", args.bw, args.sex);
+    println!("DOTS: {dots_score:.2}");
+    println!("Wilks: {:.2}", wilks(total, args.bw, args.sex));
+    println!(
+        "IPF GL ({}): {:.2}",
+        args.equipment,
+        ipf_gl(total, args.bw, args.sex, args.equipment, GlEvent::Total)
+    );
+
+    for multiple in bodyweight_multiples(args.bw, [args.squat, args.bench, args.deadlift]) {
+        let badge = multiple
+            .badge_label()
+            .map_or_else(String::new, |label| format!(" ({label})"));
+        println!(
+            "{}: {:.2}× bodyweight{badge}",
+            multiple.lift.slug(),
+            multiple.multiple
+        );
+    }
+
+    let mut class_best = None;
+    if let Some(dataset) = args.dataset {
+        let insights = IronInsights::load(&dataset)?;
+        let ladder = load_levels(args.levels.as_deref())?;
+        let pool = match args.compare_equipment {
+            EquipmentFilter::All => args.sex.to_string(),
+            EquipmentFilter::Only(equipment) => format!("{} {equipment}", args.sex),
+        };
+        let query = Query {
+            equipment: args.compare_equipment,
+            formula: Some(Formula::Dots),
+            ..Query::kg(args.sex, LiftType::Total)
+        };
+        match insights.percentile_with_confidence(&query, dots_score) {
+            Some((percentile, confidence)) => {
+                println!(
+                    "DOTS percentile: {percentile:.1} (of {} {pool} totals, 95% CI {:.1}-{:.1})",
+                    confidence.population, confidence.ci_low, confidence.ci_high
+                );
+                if !confidence.is_reliable() {
+                    println!("caution: too few comparable totals for a stable percentile");
+                }
+                if let Some(level) = ladder.level_for_lift(percentile, args.sex, LiftType::Total) {
+                    println!("level: {}", level.name);
+                }
+            }
+            None => println!("DOTS percentile: no {pool} totals in dataset"),
+        }
+        if let Some(profile) = insights.lift_profile(
+            args.sex,
+            args.compare_equipment,
+            args.bw,
+            [args.squat, args.bench, args.deadlift],
+        ) {
+            let parts: Vec<String> = profile
+                .percentiles
+                .iter()
+                .map(|(lift, percentile)| format!("{} {percentile:.1}", lift.slug()))
+                .collect();
+            println!("lift percentiles (DOTS): {}", parts.join(", "));
+            if profile.balanced {
+                println!("profile: balanced");
+            } else {
+                println!(
+                    "profile: {}-dominant, {}-lagging",
+                    profile.strongest.slug(),
+                    profile.weakest.slug()
+                );
+            }
+        }
+        let class = WeightClass::for_bodyweight(args.sex, args.bw);
+        let class_query = Query {
+            equipment: args.compare_equipment,
+            class: Some(class),
+            ..Query::kg(args.sex, LiftType::Total)
+        };
+        class_best = insights
+            .rankings(&class_query, 1)
+            .first()
+            .map(|best| (format!("{class} kg class best"), best.value));
+    }
+
+    let record = class_best.as_ref().map(|(label, kg)| (label.as_str(), *kg));
+    for milestone in total_milestones(total, record) {
+        if milestone.is_reached() {
+            println!(
+                "{}: reached ({:.1} kg)",
+                milestone.label, milestone.target_kg
+            );
+        } else {
+            println!(
+                "{}: {:.1} kg to go ({:.1} kg, {:.0}%)",
+                milestone.label,
+                milestone.remaining_kg,
+                milestone.target_kg,
+                milestone.progress_pct
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Parses one `Division=weight` pair for `gen-data --equipment-weights`.
+fn parse_equipment_weight(raw: &str) -> Result<(Equipment, f64), String> {
+    let (equipment, weight) = raw
+        .split_once('=')
+        .ok_or_else(|| format!("expected Division=weight, got {raw:?}"))?;
+    let equipment = equipment.parse().map_err(|err| format!("{err}"))?;
+    let weight: f64 = weight
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid weight {weight:?}"))?;
+    if !weight.is_finite() || weight < 0.0 {
+        return Err(format!(
+            "weight must be a non-negative number, got {weight}"
+        ));
+    }
+    Ok((equipment, weight))
+}
+
+/// Weights in [`Equipment::ALL`] order, or the generator default when none were given.
+fn equipment_weights(pairs: &[(Equipment, f64)]) -> [f64; 6] {
+    if pairs.is_empty() {
+        return GeneratorConfig::DEFAULT_EQUIPMENT_WEIGHTS;
+    }
+    Equipment::ALL.map(|equipment| {
+        pairs
+            .iter()
+            .filter(|(listed, _)| *listed == equipment)
+            .map(|(_, weight)| weight)
+            .sum()
+    })
+}
+
+fn run_gen_data(config: GeneratorConfig, out: PathBuf) -> Result<()> {
+    if !(0.0..=1.0).contains(&config.female_share) {
+        bail!("--female-share must be between 0 and 1");
+    }
+    let lifters = generate(&config);
+    fs::write(&out, serde_json::to_vec_pretty(&lifters)?)
+        .with_context(|| format!("failed to write {}", out.display()))?;
+    println!(
+        "generated {} lifters (seed {}) into {}",
+        lifters.len(),
+        config.seed,
+        out.display()
+    );
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    match Cli::parse().command {
+        Command::Import {
+            input,
+            mapping,
+            out,
+        } => run_import(input, mapping, out),
+        Command::Validate { input, mapping } => run_validate(input, mapping),
+        Command::Score(args) => run_score(args),
+        Command::GenData {
+            count,
+            seed,
+            female_share,
+            equipment_weights: weights,
+            out,
+        } => run_gen_data(
+            GeneratorConfig {
+                count,
+                seed,
+                female_share,
+                equipment_weights: equipment_weights(&weights),
+                ..GeneratorConfig::default()
+            },
+            out,
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cli, Command, ScoreArgs, equipment_weights};
+    use clap::Parser;
+    use iron_insights::model::{Equipment, EquipmentFilter};
+    use iron_insights::synthetic::GeneratorConfig;
+
+    fn score_args(overrides: &[(&str, &str)]) -> ScoreArgs {
+        let mut fields = vec![
+            ("sex", "M"),
+            ("bw", "93"),
+            ("squat", "250"),
+            ("bench", "170"),
+            ("deadlift", "300"),
+        ];
+        for (name, value) in overrides {
+            fields.retain(|(field, _)| field != name);
+            fields.push((name, value));
+        }
+        let argv = ["iron_insights".to_string(), "score".to_string()]
+            .into_iter()
+            .chain(
+                fields
+                    .iter()
+                    .map(|(name, value)| format!("--{name}={value}")),
+            );
+        match Cli::try_parse_from(argv)
+            .expect("arguments should parse")
+            .command
+        {
+            Command::Score(args) => args,
+            other => panic!("expected score, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn score_compares_against_every_division_by_default() {
+        let args = score_args(&[("dataset", "lifters.json")]);
+        assert_eq!(args.compare_equipment, EquipmentFilter::All);
+        let args = score_args(&[("dataset", "lifters.json"), ("compare-equipment", "raw")]);
+        assert_eq!(
+            args.compare_equipment,
+            EquipmentFilter::Only(Equipment::Raw)
+        );
+    }
+
+    #[test]
+    fn score_accepts_valid_lifter() {
+        assert!(score_args(&[]).check().is_ok());
+        assert!(score_args(&[("bench", "0")]).check().is_ok());
+    }
+
+    #[test]
+    fn score_rejects_unusable_bodyweight() {
+        for bw in ["0", "-80", "NaN", "inf"] {
+            assert!(score_args(&[("bw", bw)]).check().is_err(), "bw {bw}");
+        }
+    }
+
+    #[test]
+    fn score_rejects_unusable_lifts() {
+        for lift in ["squat", "bench", "deadlift"] {
+            for kg in ["-1", "NaN", "inf", "-inf"] {
+                let args = score_args(&[(lift, kg)]);
+                assert!(args.check().is_err(), "{lift} {kg}");
+            }
+        }
+    }
+
+    #[test]
+    fn gen_data_parses_equipment_weights() {
+        let parse = |extra: &[&str]| {
+            let argv = ["iron_insights", "gen-data", "--out", "lifters.json"]
+                .iter()
+                .chain(extra);
+            match Cli::try_parse_from(argv).map(|cli| cli.command) {
+                Ok(Command::GenData {
+                    equipment_weights: pairs,
+                    ..
+                }) => Ok(equipment_weights(&pairs)),
+                Ok(other) => panic!("expected gen-data, got {other:?}"),
+                Err(err) => Err(err),
+            }
+        };
+        assert_eq!(
+            parse(&[]).unwrap(),
+            GeneratorConfig::DEFAULT_EQUIPMENT_WEIGHTS
+        );
+        assert_eq!(
+            parse(&["--equipment-weights", "raw=0.8,Single-ply=0.2"]).unwrap(),
+            [0.8, 0.0, 0.2, 0.0, 0.0, 0.0]
+        );
+        assert!(parse(&["--equipment-weights", "Raw=-1"]).is_err());
+        assert!(parse(&["--equipment-weights", "Bench shirt=1"]).is_err());
+    }
 }
@@ -0,0 +1,159 @@
+//! Progress toward round-number totals, the 1000 lb club, record targets, and bodyweight
+//! multiples.
+
+use crate::model::LiftType;
+use serde::Serialize;
+
+/// Totals are tracked toward the next multiple of this many kg.
+pub const TOTAL_STEP_KG: f64 = 25.0;
+/// 1000 lb in kg.
+pub const THOUSAND_POUND_CLUB_KG: f64 = 453.592_37;
+
+/// Distance from a current result to one target.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Milestone {
+    pub label: String,
+    pub target_kg: f64,
+    /// Zero once the target is reached.
+    pub remaining_kg: f64,
+    /// 0-100, capped at 100.
+    pub progress_pct: f64,
+}
+
+impl Milestone {
+    pub fn new(label: impl Into<String>, target_kg: f64, current_kg: f64) -> Self {
+        let progress_pct = if target_kg > 0.0 {
+            (current_kg / target_kg * 100.0).clamp(0.0, 100.0)
+        } else {
+            100.0
+        };
+        Self {
+            label: label.into(),
+            target_kg,
+            remaining_kg: (target_kg - current_kg).max(0.0),
+            progress_pct,
+        }
+    }
+
+    pub fn is_reached(&self) -> bool {
+        self.remaining_kg == 0.0
+    }
+}
+
+/// Next multiple of [`TOTAL_STEP_KG`] strictly above `total_kg`.
+pub fn next_total_step(total_kg: f64) -> f64 {
+    ((total_kg / TOTAL_STEP_KG).floor() + 1.0) * TOTAL_STEP_KG
+}
+
+/// Milestones for a total: the next 25 kg step, the 1000 lb club, and an optional record.
+///
+/// `record` is a `(label, kg)` pair such as the best total in the lifter's class.
+pub fn total_milestones(total_kg: f64, record: Option<(&str, f64)>) -> Vec<Milestone> {
+    let mut milestones = vec![
+        Milestone::new("next 25 kg", next_total_step(total_kg), total_kg),
+        Milestone::new("1000 lb club", THOUSAND_POUND_CLUB_KG, total_kg),
+    ];
+    if let Some((label, kg)) = record {
+        milestones.push(Milestone::new(label, kg, total_kg));
+    }
+    milestones
+}
+
+/// Named bodyweight multiples ("2× bodyweight squat"), the same for every sex and lift.
+pub const BODYWEIGHT_MULTIPLES: [f64; 5] = [1.0, 1.5, 2.0, 2.5, 3.0];
+
+/// A single lift expressed as a multiple of bodyweight.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BodyweightMultiple {
+    pub lift: LiftType,
+    pub multiple: f64,
+    /// Highest entry of [`BODYWEIGHT_MULTIPLES`] reached, if any.
+    pub badge: Option<f64>,
+}
+
+impl BodyweightMultiple {
+    pub fn new(lift: LiftType, lift_kg: f64, bodyweight_kg: f64) -> Self {
+        let multiple = if bodyweight_kg > 0.0 {
+            lift_kg / bodyweight_kg
+        } else {
+            0.0
+        };
+        let badge = BODYWEIGHT_MULTIPLES
+            .iter()
+            .rev()
+            .find(|badge| multiple >= **badge)
+            .copied();
+        Self {
+            lift,
+            multiple,
+            badge,
+        }
+    }
+
+    /// Badge name such as `2× bodyweight squat`, if one was reached.
+    pub fn badge_label(&self) -> Option<String> {
+        self.badge
+            .map(|badge| format!("{badge}× bodyweight {}", self.lift.slug()))
+    }
+}
+
+/// Bodyweight multiples for squat, bench, and deadlift.
+pub fn bodyweight_multiples(
+    bodyweight_kg: f64,
+    [squat, bench, deadlift]: [f64; 3],
+) -> [BodyweightMultiple; 3] {
+    [
+        BodyweightMultiple::new(LiftType::Squat, squat, bodyweight_kg),
+        BodyweightMultiple::new(LiftType::Bench, bench, bodyweight_kg),
+        BodyweightMultiple::new(LiftType::Deadlift, deadlift, bodyweight_kg),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BodyweightMultiple, Milestone, bodyweight_multiples, next_total_step, total_milestones,
+    };
+    use crate::model::LiftType;
+
+    #[test]
+    fn next_step_is_strictly_above() {
+        assert_eq!(next_total_step(590.0), 600.0);
+        assert_eq!(next_total_step(600.0), 625.0);
+        assert_eq!(next_total_step(0.0), 25.0);
+    }
+
+    #[test]
+    fn progress_is_capped_and_remaining_floors_at_zero() {
+        let reached = Milestone::new("club", 453.6, 500.0);
+        assert!(reached.is_reached());
+        assert_eq!(reached.progress_pct, 100.0);
+
+        let halfway = Milestone::new("record", 800.0, 400.0);
+        assert_eq!(halfway.remaining_kg, 400.0);
+        assert_eq!(halfway.progress_pct, 50.0);
+    }
+
+    #[test]
+    fn total_milestones_include_optional_record() {
+        assert_eq!(total_milestones(400.0, None).len(), 2);
+        let milestones = total_milestones(400.0, Some(("class record", 700.0)));
+        assert_eq!(milestones[2].label, "class record");
+        assert!((milestones[1].remaining_kg - 53.592_37).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bodyweight_multiples_pick_highest_badge() {
+        let [squat, bench, deadlift] = bodyweight_multiples(80.0, [200.0, 70.0, 240.0]);
+        assert_eq!(squat.multiple, 2.5);
+        assert_eq!(
+            squat.badge_label().as_deref(),
+            Some("2.5× bodyweight squat")
+        );
+        assert_eq!(bench.badge, None);
+        assert_eq!(deadlift.badge, Some(3.0));
+
+        let zero = BodyweightMultiple::new(LiftType::Bench, 100.0, 0.0);
+        assert_eq!(zero.badge, None);
+    }
+}
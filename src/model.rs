@@ -0,0 +1,588 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Error returned when a filter string does not name a known value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseKindError {
+    pub kind: &'static str,
+    pub value: String,
+}
+
+impl fmt::Display for ParseKindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown {}: {:?}", self.kind, self.value)
+    }
+}
+
+impl std::error::Error for ParseKindError {}
+
+/// Lifter sex, serialized as the published `M` / `F` codes.
+///
+/// Deserializing accepts every spelling [`FromStr`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl Sex {
+    pub const ALL: [Sex; 2] = [Sex::Male, Sex::Female];
+
+    /// Published code used in slice keys (`sex=M`).
+    pub fn code(self) -> &'static str {
+        match self {
+            Sex::Male => "M",
+            Sex::Female => "F",
+        }
+    }
+
+    /// Lowercase path segment used in payload paths (`bin/m/...`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            Sex::Male => "m",
+            Sex::Female => "f",
+        }
+    }
+}
+
+impl fmt::Display for Sex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Sex {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "m" | "male" => Ok(Sex::Male),
+            "f" | "female" => Ok(Sex::Female),
+            _ => Err(ParseKindError {
+                kind: "sex",
+                value: s.to_string(),
+            }),
+        }
+    }
+}
+
+impl Serialize for Sex {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for Sex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Spellings seen in meet results and gym sheets that mean a published division.
+const EQUIPMENT_ALIASES: [(&str, Equipment); 8] = [
+    ("classic", Equipment::Raw),
+    ("sleeves", Equipment::Raw),
+    ("knee wraps", Equipment::Wraps),
+    ("single ply", Equipment::SinglePly),
+    ("singleply", Equipment::SinglePly),
+    ("multi ply", Equipment::MultiPly),
+    ("multiply", Equipment::MultiPly),
+    ("wrist straps", Equipment::Straps),
+];
+
+/// Equipment division, serialized with the OpenPowerlifting labels.
+///
+/// Deserializing accepts every spelling [`Equipment::normalize`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Equipment {
+    Raw,
+    Wraps,
+    SinglePly,
+    MultiPly,
+    Unlimited,
+    Straps,
+}
+
+impl Equipment {
+    pub const ALL: [Equipment; 6] = [
+        Equipment::Raw,
+        Equipment::Wraps,
+        Equipment::SinglePly,
+        Equipment::MultiPly,
+        Equipment::Unlimited,
+        Equipment::Straps,
+    ];
+
+    /// Published label used in slice keys (`equip=Single-ply`).
+    pub fn label(self) -> &'static str {
+        match self {
+            Equipment::Raw => "Raw",
+            Equipment::Wraps => "Wraps",
+            Equipment::SinglePly => "Single-ply",
+            Equipment::MultiPly => "Multi-ply",
+            Equipment::Unlimited => "Unlimited",
+            Equipment::Straps => "Straps",
+        }
+    }
+
+    /// Lowercase path segment used in payload paths (`bin/m/single-ply/...`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            Equipment::Raw => "raw",
+            Equipment::Wraps => "wraps",
+            Equipment::SinglePly => "single-ply",
+            Equipment::MultiPly => "multi-ply",
+            Equipment::Unlimited => "unlimited",
+            Equipment::Straps => "straps",
+        }
+    }
+
+    /// Whether the division allows supportive suits or shirts.
+    pub fn is_equipped(self) -> bool {
+        matches!(
+            self,
+            Equipment::SinglePly | Equipment::MultiPly | Equipment::Unlimited
+        )
+    }
+
+    /// Maps a raw equipment value onto a published division, or `None` if unrecognized.
+    ///
+    /// Accepts labels, slugs, and common spellings such as `Classic` or `Multiply`,
+    /// ignoring case and surrounding whitespace.
+    pub fn normalize(raw: &str) -> Option<Equipment> {
+        let raw = raw.trim();
+        Equipment::ALL
+            .into_iter()
+            .find(|equipment| {
+                equipment.label().eq_ignore_ascii_case(raw)
+                    || equipment.slug().eq_ignore_ascii_case(raw)
+            })
+            .or_else(|| {
+                EQUIPMENT_ALIASES
+                    .iter()
+                    .find(|(alias, _)| alias.eq_ignore_ascii_case(raw))
+                    .map(|(_, equipment)| *equipment)
+            })
+    }
+}
+
+impl fmt::Display for Equipment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for Equipment {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Equipment::normalize(s).ok_or_else(|| ParseKindError {
+            kind: "equipment",
+            value: s.to_string(),
+        })
+    }
+}
+
+impl Serialize for Equipment {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.label())
+    }
+}
+
+impl<'de> Deserialize<'de> for Equipment {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Equipment filter; `All` is the published pooled shard (`equip=All`).
+///
+/// Serialized as its label; deserializing accepts every spelling [`FromStr`] does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EquipmentFilter {
+    #[default]
+    All,
+    Only(Equipment),
+}
+
+impl EquipmentFilter {
+    /// Published label used in shard keys (`equip=All`, `equip=Raw`).
+    pub fn label(self) -> &'static str {
+        match self {
+            EquipmentFilter::All => "All",
+            EquipmentFilter::Only(equipment) => equipment.label(),
+        }
+    }
+
+    /// Lowercase path segment used in shard paths (`index_shards/m/all/...`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            EquipmentFilter::All => "all",
+            EquipmentFilter::Only(equipment) => equipment.slug(),
+        }
+    }
+
+    pub fn matches(self, equipment: Equipment) -> bool {
+        match self {
+            EquipmentFilter::All => true,
+            EquipmentFilter::Only(only) => only == equipment,
+        }
+    }
+}
+
+impl fmt::Display for EquipmentFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label())
+    }
+}
+
+impl FromStr for EquipmentFilter {
+    type Err = ParseKindError;
+
+    /// Accepts `All` or `Any` for the pooled shard, otherwise any [`Equipment`] spelling.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        if trimmed.eq_ignore_ascii_case("all") || trimmed.eq_ignore_ascii_case("any") {
+            return Ok(EquipmentFilter::All);
+        }
+        s.parse().map(EquipmentFilter::Only)
+    }
+}
+
+impl Serialize for EquipmentFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.label())
+    }
+}
+
+impl<'de> Deserialize<'de> for EquipmentFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Competition lift, serialized as the published `S` / `B` / `D` / `T` codes.
+///
+/// Deserializing accepts every spelling [`FromStr`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiftType {
+    Squat,
+    Bench,
+    Deadlift,
+    Total,
+}
+
+impl LiftType {
+    pub const ALL: [LiftType; 4] = [
+        LiftType::Squat,
+        LiftType::Bench,
+        LiftType::Deadlift,
+        LiftType::Total,
+    ];
+
+    /// Published code used in slice keys (`lift=S`).
+    pub fn code(self) -> &'static str {
+        match self {
+            LiftType::Squat => "S",
+            LiftType::Bench => "B",
+            LiftType::Deadlift => "D",
+            LiftType::Total => "T",
+        }
+    }
+
+    /// Lowercase name used in payload file names (`squat.bin`).
+    pub fn slug(self) -> &'static str {
+        match self {
+            LiftType::Squat => "squat",
+            LiftType::Bench => "bench",
+            LiftType::Deadlift => "deadlift",
+            LiftType::Total => "total",
+        }
+    }
+}
+
+impl fmt::Display for LiftType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for LiftType {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        LiftType::ALL
+            .into_iter()
+            .find(|lift| {
+                lift.code().eq_ignore_ascii_case(s.trim())
+                    || lift.slug().eq_ignore_ascii_case(s.trim())
+            })
+            .ok_or_else(|| ParseKindError {
+                kind: "lift",
+                value: s.to_string(),
+            })
+    }
+}
+
+impl Serialize for LiftType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.code())
+    }
+}
+
+impl<'de> Deserialize<'de> for LiftType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Upper limits (kg) of the IPF weight classes for men, excluding the open-ended top class.
+/// 53 kg is the sub-junior and junior class; the rest are open classes.
+pub const IPF_CLASSES_MALE: [u16; 8] = [53, 59, 66, 74, 83, 93, 105, 120];
+/// Upper limits (kg) of the IPF weight classes for women, excluding the open-ended top class.
+/// 43 kg is the sub-junior and junior class; the rest are open classes.
+pub const IPF_CLASSES_FEMALE: [u16; 8] = [43, 47, 52, 57, 63, 69, 76, 84];
+
+/// IPF weight class, published as the class limit (`wc=105`, `wc=120+`) and as a path
+/// segment (`bin/m/raw/120_/...`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum WeightClass {
+    /// Bodyweight at or below this many kg.
+    UpTo(u16),
+    /// Bodyweight above this many kg.
+    Above(u16),
+}
+
+impl WeightClass {
+    /// Class a lifter of `bodyweight` kg falls in, as bucketed in the published data.
+    pub fn for_bodyweight(sex: Sex, bodyweight: f64) -> WeightClass {
+        let limits = match sex {
+            Sex::Male => &IPF_CLASSES_MALE,
+            Sex::Female => &IPF_CLASSES_FEMALE,
+        };
+        limits
+            .iter()
+            .find(|limit| bodyweight <= f64::from(**limit))
+            .map_or(WeightClass::Above(limits[limits.len() - 1]), |limit| {
+                WeightClass::UpTo(*limit)
+            })
+    }
+
+    /// Path segment used in payload paths (`105`, `120_`).
+    pub fn slug(self) -> String {
+        match self {
+            WeightClass::UpTo(limit) => limit.to_string(),
+            WeightClass::Above(limit) => format!("{limit}_"),
+        }
+    }
+}
+
+impl fmt::Display for WeightClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WeightClass::UpTo(limit) => write!(f, "{limit}"),
+            WeightClass::Above(limit) => write!(f, "{limit}+"),
+        }
+    }
+}
+
+impl Serialize for WeightClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for WeightClass {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl FromStr for WeightClass {
+    type Err = ParseKindError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let (digits, above) = match trimmed
+            .strip_suffix('+')
+            .or_else(|| trimmed.strip_suffix('_'))
+        {
+            Some(digits) => (digits, true),
+            None => (trimmed, false),
+        };
+        let limit: u16 = digits.parse().map_err(|_| ParseKindError {
+            kind: "weight class",
+            value: s.to_string(),
+        })?;
+        Ok(if above {
+            WeightClass::Above(limit)
+        } else {
+            WeightClass::UpTo(limit)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Equipment, EquipmentFilter, LiftType, Sex, WeightClass};
+
+    #[test]
+    fn parses_published_codes_and_slugs() {
+        assert_eq!("M".parse(), Ok(Sex::Male));
+        assert_eq!("female".parse(), Ok(Sex::Female));
+        assert_eq!("Single-ply".parse(), Ok(Equipment::SinglePly));
+        assert_eq!("multi-ply".parse(), Ok(Equipment::MultiPly));
+        assert_eq!("T".parse(), Ok(LiftType::Total));
+        assert_eq!("squat".parse(), Ok(LiftType::Squat));
+        assert!("X".parse::<Sex>().is_err());
+        assert!("Suit".parse::<Equipment>().is_err());
+    }
+
+    #[test]
+    fn normalizes_equipment_spellings() {
+        assert_eq!(Equipment::normalize(" Classic "), Some(Equipment::Raw));
+        assert_eq!(
+            Equipment::normalize("SINGLE PLY"),
+            Some(Equipment::SinglePly)
+        );
+        assert_eq!(Equipment::normalize("Multiply"), Some(Equipment::MultiPly));
+        assert_eq!(Equipment::normalize("Straps"), Some(Equipment::Straps));
+        assert_eq!(Equipment::normalize(""), None);
+
+        let err = "Bionic".parse::<Equipment>().unwrap_err();
+        assert_eq!(err.to_string(), "unknown equipment: \"Bionic\"");
+
+        assert_eq!(
+            serde_json::from_str::<Equipment>("\"Classic\"").unwrap(),
+            Equipment::Raw
+        );
+        assert!(serde_json::from_str::<Equipment>("\"Bionic\"").is_err());
+    }
+
+    #[test]
+    fn equipment_filter_matches_published_shards() {
+        assert_eq!("Any".parse(), Ok(EquipmentFilter::All));
+        assert_eq!("all".parse(), Ok(EquipmentFilter::All));
+        assert_eq!("raw".parse(), Ok(EquipmentFilter::Only(Equipment::Raw)));
+        assert!("Bionic".parse::<EquipmentFilter>().is_err());
+        assert_eq!(EquipmentFilter::All.slug(), "all");
+        assert_eq!(
+            EquipmentFilter::Only(Equipment::SinglePly).label(),
+            "Single-ply"
+        );
+        assert!(EquipmentFilter::All.matches(Equipment::Wraps));
+        assert!(!EquipmentFilter::Only(Equipment::Raw).matches(Equipment::Wraps));
+    }
+
+    #[test]
+    fn weight_classes_follow_ipf_limits() {
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Male, 93.0),
+            WeightClass::UpTo(93)
+        );
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Male, 93.1),
+            WeightClass::UpTo(105)
+        );
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Male, 140.0),
+            WeightClass::Above(120)
+        );
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Female, 45.0),
+            WeightClass::UpTo(47)
+        );
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Male, 52.0),
+            WeightClass::UpTo(53)
+        );
+        assert_eq!(
+            WeightClass::for_bodyweight(Sex::Female, 42.5),
+            WeightClass::UpTo(43)
+        );
+        assert_eq!(WeightClass::Above(120).to_string(), "120+");
+        assert_eq!("120+".parse(), Ok(WeightClass::Above(120)));
+        assert_eq!("120_".parse(), Ok(WeightClass::Above(120)));
+        assert_eq!(WeightClass::Above(84).slug(), "84_");
+        assert_eq!(WeightClass::UpTo(53).slug(), "53");
+        assert_eq!("105".parse(), Ok(WeightClass::UpTo(105)));
+        assert!("All".parse::<WeightClass>().is_err());
+        assert_eq!(
+            serde_json::to_string(&WeightClass::Above(84)).unwrap(),
+            "\"84+\""
+        );
+        assert_eq!(
+            serde_json::from_str::<WeightClass>("\"63\"").unwrap(),
+            WeightClass::UpTo(63)
+        );
+    }
+
+    #[test]
+    fn display_roundtrips_through_from_str() {
+        for sex in Sex::ALL {
+            assert_eq!(sex.to_string().parse(), Ok(sex));
+        }
+        for equipment in Equipment::ALL {
+            assert_eq!(equipment.to_string().parse(), Ok(equipment));
+            assert_eq!(equipment.slug().parse(), Ok(equipment));
+        }
+        for lift in LiftType::ALL {
+            assert_eq!(lift.to_string().parse(), Ok(lift));
+            assert_eq!(lift.slug().parse(), Ok(lift));
+        }
+    }
+
+    #[test]
+    fn serde_uses_published_labels() {
+        assert_eq!(serde_json::to_string(&Sex::Female).unwrap(), "\"F\"");
+        assert_eq!(
+            serde_json::to_string(&Equipment::SinglePly).unwrap(),
+            "\"Single-ply\""
+        );
+        assert_eq!(
+            serde_json::from_str::<LiftType>("\"bench\"").unwrap(),
+            LiftType::Bench
+        );
+        assert_eq!(serde_json::to_string(&LiftType::Squat).unwrap(), "\"S\"");
+    }
+
+    #[test]
+    fn serde_accepts_every_from_str_spelling() {
+        for (raw, sex) in [("\"MALE\"", Sex::Male), ("\" f \"", Sex::Female)] {
+            assert_eq!(serde_json::from_str::<Sex>(raw).unwrap(), sex, "{raw}");
+        }
+        for (raw, lift) in [("\"Squat\"", LiftType::Squat), ("\"t\"", LiftType::Total)] {
+            assert_eq!(
+                serde_json::from_str::<LiftType>(raw).unwrap(),
+                lift,
+                "{raw}"
+            );
+        }
+        assert!(serde_json::from_str::<Sex>("\"X\"").is_err());
+        assert!(serde_json::from_str::<LiftType>("\"clean\"").is_err());
+
+        assert_eq!(
+            serde_json::to_string(&EquipmentFilter::Only(Equipment::SinglePly)).unwrap(),
+            "\"Single-ply\""
+        );
+        assert_eq!(
+            serde_json::from_str::<EquipmentFilter>("\"Any\"").unwrap(),
+            EquipmentFilter::All
+        );
+        assert_eq!(
+            serde_json::from_str::<EquipmentFilter>("\"classic\"").unwrap(),
+            EquipmentFilter::Only(Equipment::Raw)
+        );
+    }
+}
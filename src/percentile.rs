@@ -0,0 +1,854 @@
+use crate::binary_counts::{HistogramHeader, QuantileHeader};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How a value's position in the population is turned into a percentile.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum PercentileMethod {
+    /// Share of the population strictly below the value (coarse, biased low at ties).
+    Exact,
+    /// Mid-rank at ties and linear interpolation between neighbouring order statistics,
+    /// or within a bin for histogram data.
+    #[default]
+    Interpolated,
+}
+
+/// Rounds a percentile to the one decimal place reported to users.
+pub fn round_percentile(percentile: f64) -> f64 {
+    (percentile * 10.0).round() / 10.0
+}
+
+/// Percentile lookup over a single filtered population.
+///
+/// Percentiles are returned on a 0-100 scale.
+pub trait PercentileEngine {
+    /// Number of values in the population.
+    fn population(&self) -> u64;
+
+    /// Percentile of `value` using `method`, or `None` for an empty population or a
+    /// non-finite value.
+    fn percentile_with(&self, value: f64, method: PercentileMethod) -> Option<f64>;
+
+    /// Percentile of `value` using the default [`PercentileMethod`].
+    fn percentile(&self, value: f64) -> Option<f64> {
+        self.percentile_with(value, PercentileMethod::default())
+    }
+
+    /// Percentile rounded to one decimal place, as shown to users.
+    fn display_percentile(&self, value: f64, method: PercentileMethod) -> Option<f64> {
+        self.percentile_with(value, method).map(round_percentile)
+    }
+}
+
+/// Exact backend over raw values, sorted once at construction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortedPercentiles {
+    values: Vec<f64>,
+}
+
+impl SortedPercentiles {
+    /// Builds the backend, dropping non-finite values.
+    pub fn new(mut values: Vec<f64>) -> Self {
+        values.retain(|v| v.is_finite());
+        values.sort_by(f64::total_cmp);
+        Self { values }
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+}
+
+impl PercentileEngine for SortedPercentiles {
+    fn population(&self) -> u64 {
+        self.values.len() as u64
+    }
+
+    fn percentile_with(&self, value: f64, method: PercentileMethod) -> Option<f64> {
+        if self.values.is_empty() || !value.is_finite() {
+            return None;
+        }
+        let n = self.values.len() as f64;
+        let below = self.values.partition_point(|v| *v < value);
+        if method == PercentileMethod::Exact {
+            return Some(below as f64 * 100.0 / n);
+        }
+
+        let not_above = self.values.partition_point(|v| *v <= value);
+        if not_above > below {
+            // `value` is present: use the mid-rank of its ties.
+            let mid_rank = below as f64 + (not_above - below) as f64 / 2.0;
+            return Some(mid_rank * 100.0 / n);
+        }
+        if below == 0 || below == self.values.len() {
+            return Some(below as f64 * 100.0 / n);
+        }
+
+        // Interpolate between the mid-ranks of the neighbouring distinct values.
+        let lo_value = self.values[below - 1];
+        let hi_value = self.values[below];
+        let lo_start = self.values.partition_point(|v| *v < lo_value);
+        let hi_end = self.values.partition_point(|v| *v <= hi_value);
+        let lo_rank = (lo_start + below) as f64 / 2.0;
+        let hi_rank = (below + hi_end) as f64 / 2.0;
+        let t = (value - lo_value) / (hi_value - lo_value);
+        Some((lo_rank + t * (hi_rank - lo_rank)) * 100.0 / n)
+    }
+}
+
+/// Backend over published histogram counts, where only bin membership is known.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramPercentiles {
+    min: f64,
+    bin_size: f64,
+    counts: Vec<u32>,
+    total: u64,
+}
+
+impl HistogramPercentiles {
+    /// Builds the backend from bin 0's lower edge, the bin width, and per-bin counts.
+    pub fn new(min: f64, bin_size: f64, counts: Vec<u32>) -> Self {
+        assert!(bin_size > 0.0, "bin_size must be > 0");
+        let total = counts.iter().map(|&c| c as u64).sum();
+        Self {
+            min,
+            bin_size,
+            counts,
+            total,
+        }
+    }
+
+    /// Builds the backend from a decoded `IIH1` histogram, or `None` if the header's bin
+    /// size is not a positive finite number or its minimum is not finite.
+    pub fn from_header(header: &HistogramHeader, counts: Vec<u32>) -> Option<Self> {
+        let bin_size = header.base_bin_size as f64;
+        if !bin_size.is_finite() || bin_size <= 0.0 || !header.min.is_finite() {
+            return None;
+        }
+        Some(Self::new(header.min as f64, bin_size, counts))
+    }
+
+    pub fn counts(&self) -> &[u32] {
+        &self.counts
+    }
+}
+
+impl PercentileEngine for HistogramPercentiles {
+    fn population(&self) -> u64 {
+        self.total
+    }
+
+    /// `Exact` counts every bin whose upper edge is at or below `value`; `Interpolated`
+    /// also adds the share of the containing bin that lies below `value`.
+    fn percentile_with(&self, value: f64, method: PercentileMethod) -> Option<f64> {
+        if self.total == 0 || !value.is_finite() {
+            return None;
+        }
+        let position = ((value - self.min) / self.bin_size).clamp(0.0, self.counts.len() as f64);
+        let full_bins = position.floor() as usize;
+        let mut below = self.counts[..full_bins]
+            .iter()
+            .map(|&c| c as f64)
+            .sum::<f64>();
+        if method == PercentileMethod::Interpolated && full_bins < self.counts.len() {
+            below += self.counts[full_bins] as f64 * position.fract();
+        }
+        Some(below * 100.0 / self.total as f64)
+    }
+}
+
+/// Quantiles stored by default in a sketch: every percentile from 0 to 100.
+pub const DEFAULT_SKETCH_POINTS: usize = 101;
+
+/// Backend over a compact quantile sketch: values at evenly spaced ranks from the minimum
+/// to the maximum. A few hundred bytes stand in for the whole population.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SketchPercentiles {
+    quantiles: Vec<f64>,
+    population: u64,
+}
+
+impl SketchPercentiles {
+    /// Builds the backend from stored quantiles and the population they summarize.
+    pub fn new(quantiles: Vec<f64>, population: u64) -> Self {
+        assert!(
+            quantiles.windows(2).all(|w| w[0] <= w[1]),
+            "quantiles must be non-decreasing"
+        );
+        Self {
+            quantiles,
+            population,
+        }
+    }
+
+    /// Builds the backend from a sketch checked by
+    /// [`crate::binary_counts::decode_quantile_sketch`].
+    pub fn from_header(header: &QuantileHeader, quantiles: Vec<f32>) -> Self {
+        Self::new(
+            quantiles.into_iter().map(f64::from).collect(),
+            header.population as u64,
+        )
+    }
+
+    /// Summarizes `sorted` with `points` quantiles, interpolating between order statistics.
+    pub fn from_sorted(sorted: &SortedPercentiles, points: usize) -> Self {
+        let values = sorted.values();
+        if values.is_empty() || points < 2 {
+            return Self::new(Vec::new(), values.len() as u64);
+        }
+        let last = (values.len() - 1) as f64;
+        let quantiles = (0..points)
+            .map(|i| {
+                let position = i as f64 / (points - 1) as f64 * last;
+                let lo = position.floor() as usize;
+                let hi = position.ceil() as usize;
+                values[lo] + (values[hi] - values[lo]) * position.fract()
+            })
+            .collect();
+        Self::new(quantiles, values.len() as u64)
+    }
+
+    pub fn quantiles(&self) -> &[f64] {
+        &self.quantiles
+    }
+
+    /// Stored quantiles as `f32`, ready for [`crate::binary_counts::encode_quantile_sketch`].
+    pub fn to_header(&self, version: u16) -> (QuantileHeader, Vec<f32>) {
+        (
+            QuantileHeader {
+                version,
+                population: self.population.min(u32::MAX as u64) as u32,
+                points: self.quantiles.len() as u32,
+            },
+            self.quantiles.iter().map(|&q| q as f32).collect(),
+        )
+    }
+}
+
+impl PercentileEngine for SketchPercentiles {
+    fn population(&self) -> u64 {
+        self.population
+    }
+
+    /// Inverts the piecewise-linear quantile curve. Ties use the middle of the tied ranks for
+    /// `Interpolated` and the lowest for `Exact`.
+    fn percentile_with(&self, value: f64, method: PercentileMethod) -> Option<f64> {
+        if self.quantiles.len() < 2 || self.population == 0 || !value.is_finite() {
+            return None;
+        }
+        let step = 100.0 / (self.quantiles.len() - 1) as f64;
+        let below = self.quantiles.partition_point(|q| *q < value);
+        let not_above = self.quantiles.partition_point(|q| *q <= value);
+        if not_above > below {
+            let first = below as f64 * step;
+            return Some(match method {
+                PercentileMethod::Exact => first,
+                PercentileMethod::Interpolated => (first + (not_above - 1) as f64 * step) / 2.0,
+            });
+        }
+        if below == 0 {
+            return Some(0.0);
+        }
+        if below == self.quantiles.len() {
+            return Some(100.0);
+        }
+        let lo = self.quantiles[below - 1];
+        let hi = self.quantiles[below];
+        Some(((below - 1) as f64 + (value - lo) / (hi - lo)) * step)
+    }
+}
+
+/// Populations smaller than this are flagged as too small for a stable percentile.
+pub const DEFAULT_MIN_SAMPLE: u64 = 30;
+/// 95% confidence intervals wider than this many percentile points are flagged as wide.
+pub const WIDE_INTERVAL_POINTS: f64 = 20.0;
+
+/// How much a percentile can be trusted given the size of the filtered population.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct PercentileConfidence {
+    pub population: u64,
+    /// Lower bound of the 95% Wilson score interval, 0-100.
+    pub ci_low: f64,
+    /// Upper bound of the 95% Wilson score interval, 0-100.
+    pub ci_high: f64,
+    pub small_sample: bool,
+    pub wide_interval: bool,
+}
+
+impl PercentileConfidence {
+    /// Whether a caution badge can be omitted.
+    pub fn is_reliable(&self) -> bool {
+        !self.small_sample && !self.wide_interval
+    }
+}
+
+/// Confidence indicator for `percentile` (0-100) measured over `population` values.
+///
+/// Uses the Wilson score interval, which stays inside 0-100 and behaves sensibly for
+/// tiny populations and percentiles near the extremes. A non-finite `percentile` gets the
+/// full 0-100 interval and is never reliable.
+pub fn percentile_confidence(percentile: f64, population: u64, min_n: u64) -> PercentileConfidence {
+    const Z: f64 = 1.96;
+
+    let (ci_low, ci_high) = if population == 0 || !percentile.is_finite() {
+        (0.0, 100.0)
+    } else {
+        let n = population as f64;
+        let p = (percentile / 100.0).clamp(0.0, 1.0);
+        let z2 = Z * Z;
+        let denominator = 1.0 + z2 / n;
+        let centre = (p + z2 / (2.0 * n)) / denominator;
+        let margin = Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+        (
+            ((centre - margin) * 100.0).max(0.0),
+            ((centre + margin) * 100.0).min(100.0),
+        )
+    };
+
+    PercentileConfidence {
+        population,
+        ci_low,
+        ci_high,
+        small_sample: population < min_n,
+        wide_interval: ci_high - ci_low > WIDE_INTERVAL_POINTS,
+    }
+}
+
+/// Hit, miss, eviction, and expiry counters for a [`PercentileCache`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub expirations: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    engine: Arc<SortedPercentiles>,
+    bytes: usize,
+    last_used: u64,
+    inserted: Instant,
+}
+
+/// Sorted populations cached per filter signature (e.g. a slice key), so repeated lookups
+/// against the same filters cost one binary search instead of a re-sort.
+///
+/// Entries belong to one data version; switching versions drops them all. With a byte
+/// budget, the least recently used entries are evicted to stay under it; with a TTL,
+/// entries older than it are rebuilt on their next lookup.
+#[derive(Debug)]
+pub struct PercentileCache<K> {
+    data_version: String,
+    entries: HashMap<K, CacheEntry>,
+    /// Keys by last use, oldest first.
+    recency: BTreeMap<u64, K>,
+    max_bytes: Option<usize>,
+    ttl: Option<Duration>,
+    bytes: usize,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl<K: Eq + Hash + Clone> PercentileCache<K> {
+    pub fn new(data_version: impl Into<String>) -> Self {
+        Self {
+            data_version: data_version.into(),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            max_bytes: None,
+            ttl: None,
+            bytes: 0,
+            clock: 0,
+            stats: CacheStats::default(),
+        }
+    }
+
+    /// Cache that keeps the total size of cached values at or below `max_bytes`.
+    pub fn with_max_bytes(data_version: impl Into<String>, max_bytes: usize) -> Self {
+        Self {
+            max_bytes: Some(max_bytes),
+            ..Self::new(data_version)
+        }
+    }
+
+    /// Expires entries `ttl` after they were built.
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        Self {
+            ttl: Some(ttl),
+            ..self
+        }
+    }
+
+    pub fn data_version(&self) -> &str {
+        &self.data_version
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Approximate memory held by cached values.
+    pub fn bytes(&self) -> usize {
+        self.bytes
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats
+    }
+
+    /// Returns the cached population for `key`, building it from `load` on a miss or
+    /// after the entry has expired.
+    ///
+    /// A population larger than the whole byte budget is returned but not cached.
+    pub fn get_or_build(
+        &mut self,
+        key: K,
+        load: impl FnOnce() -> Vec<f64>,
+    ) -> Arc<SortedPercentiles> {
+        self.get_or_build_at(key, Instant::now(), load)
+    }
+
+    fn get_or_build_at(
+        &mut self,
+        key: K,
+        now: Instant,
+        load: impl FnOnce() -> Vec<f64>,
+    ) -> Arc<SortedPercentiles> {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get(&key) {
+            if self.is_expired(entry, now) {
+                self.remove(&key);
+                self.stats.expirations += 1;
+            } else {
+                let engine = entry.engine.clone();
+                self.touch(&key);
+                self.stats.hits += 1;
+                return engine;
+            }
+        }
+        self.stats.misses += 1;
+
+        let engine = Arc::new(SortedPercentiles::new(load()));
+        let bytes = std::mem::size_of_val(engine.values());
+        if self.max_bytes.is_some_and(|max| bytes > max) {
+            return engine;
+        }
+        while self.max_bytes.is_some_and(|max| self.bytes + bytes > max) {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.bytes -= entry.bytes;
+                self.stats.evictions += 1;
+            }
+        }
+        self.bytes += bytes;
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                engine: engine.clone(),
+                bytes,
+                last_used: self.clock,
+                inserted: now,
+            },
+        );
+        engine
+    }
+
+    /// Drops every expired entry, returning how many were removed. Meant for a periodic
+    /// cleanup task; lookups already skip expired entries on their own.
+    pub fn purge_expired(&mut self) -> usize {
+        self.purge_expired_at(Instant::now())
+    }
+
+    fn purge_expired_at(&mut self, now: Instant) -> usize {
+        let expired: Vec<K> = self
+            .entries
+            .iter()
+            .filter(|(_, entry)| self.is_expired(entry, now))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.remove(key);
+        }
+        self.stats.expirations += expired.len() as u64;
+        expired.len()
+    }
+
+    fn is_expired(&self, entry: &CacheEntry, now: Instant) -> bool {
+        self.ttl
+            .is_some_and(|ttl| now.saturating_duration_since(entry.inserted) >= ttl)
+    }
+
+    fn touch(&mut self, key: &K) {
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.recency.remove(&entry.last_used);
+            entry.last_used = self.clock;
+            self.recency.insert(self.clock, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+            self.bytes -= entry.bytes;
+        }
+    }
+
+    /// Switches to `data_version`, clearing every entry if it differs from the current one.
+    ///
+    /// Returns `true` when the cache was invalidated.
+    pub fn set_data_version(&mut self, data_version: &str) -> bool {
+        if self.data_version == data_version {
+            return false;
+        }
+        self.data_version = data_version.to_string();
+        self.entries.clear();
+        self.recency.clear();
+        self.bytes = 0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        DEFAULT_MIN_SAMPLE, DEFAULT_SKETCH_POINTS, HistogramPercentiles, PercentileCache,
+        PercentileEngine, PercentileMethod, SketchPercentiles, SortedPercentiles,
+        percentile_confidence, round_percentile,
+    };
+    use crate::binary_counts::{HistogramHeader, decode_quantile_sketch, encode_quantile_sketch};
+    use std::time::{Duration, Instant};
+
+    fn sample_values() -> Vec<f64> {
+        // Deterministic, roughly bell-shaped spread between 100 and 300.
+        (0..2_000)
+            .map(|i| {
+                let a = (i * 7919 % 1000) as f64 / 1000.0;
+                let b = (i * 104_729 % 1000) as f64 / 1000.0;
+                100.0 + 100.0 * (a + b)
+            })
+            .collect()
+    }
+
+    fn histogram_of(values: &[f64], min: f64, bin_size: f64, bins: usize) -> Vec<u32> {
+        let mut counts = vec![0u32; bins];
+        for v in values {
+            let idx = (((v - min) / bin_size).floor() as usize).min(bins - 1);
+            counts[idx] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn sorted_counts_strictly_below() {
+        let engine = SortedPercentiles::new(vec![3.0, 1.0, 2.0, 2.0, f64::NAN]);
+        assert_eq!(engine.population(), 4);
+        let p = |v| engine.percentile_with(v, PercentileMethod::Exact);
+        assert_eq!(p(2.0), Some(25.0));
+        assert_eq!(p(2.5), Some(75.0));
+        assert_eq!(p(0.0), Some(0.0));
+        assert_eq!(p(10.0), Some(100.0));
+    }
+
+    #[test]
+    fn empty_population_has_no_percentile() {
+        assert_eq!(SortedPercentiles::new(vec![]).percentile(1.0), None);
+        assert_eq!(
+            HistogramPercentiles::new(0.0, 1.0, vec![0, 0]).percentile(1.0),
+            None
+        );
+    }
+
+    #[test]
+    fn histogram_backend_reads_header() {
+        let header = HistogramHeader {
+            version: 1,
+            base_bin_size: 10.0,
+            min: 100.0,
+            max: 140.0,
+            bins: 4,
+        };
+        let engine = HistogramPercentiles::from_header(&header, vec![1, 1, 1, 1]).unwrap();
+        let p = |v| engine.percentile_with(v, PercentileMethod::Exact);
+        assert_eq!(p(125.0), Some(50.0));
+        assert_eq!(p(50.0), Some(0.0));
+        assert_eq!(p(500.0), Some(100.0));
+    }
+
+    #[test]
+    fn histogram_header_with_bad_bin_size_is_rejected() {
+        for base_bin_size in [0.0, -2.5, f32::NAN, f32::INFINITY] {
+            let header = HistogramHeader {
+                version: 1,
+                base_bin_size,
+                min: 100.0,
+                max: 140.0,
+                bins: 2,
+            };
+            assert_eq!(HistogramPercentiles::from_header(&header, vec![1, 1]), None);
+        }
+    }
+
+    #[test]
+    fn backends_agree_within_one_bin() {
+        let values = sample_values();
+        let (min, bin_size, bins) = (100.0, 2.5, 80);
+        let exact = SortedPercentiles::new(values.clone());
+        let binned =
+            HistogramPercentiles::new(min, bin_size, histogram_of(&values, min, bin_size, bins));
+
+        for step in 0..=80 {
+            let value = 100.0 + step as f64 * 2.5 + 1.0;
+            let a = exact
+                .percentile_with(value, PercentileMethod::Exact)
+                .unwrap();
+            let b = binned
+                .percentile_with(value, PercentileMethod::Exact)
+                .unwrap();
+            // The histogram can only miss the mass inside the bin containing `value`.
+            assert!(b <= a + 1e-9, "histogram overshoots at {value}: {b} > {a}");
+            assert!(a - b <= 3.0, "backends diverge at {value}: {a} vs {b}");
+        }
+    }
+
+    #[test]
+    fn interpolated_uses_mid_rank_at_ties() {
+        let engine = SortedPercentiles::new(vec![1.0, 2.0, 2.0, 3.0]);
+        let p = |v| engine.percentile_with(v, PercentileMethod::Interpolated);
+        assert_eq!(p(2.0), Some(50.0));
+        assert_eq!(p(1.0), Some(12.5));
+        assert_eq!(p(0.5), Some(0.0));
+        assert_eq!(p(4.0), Some(100.0));
+    }
+
+    #[test]
+    fn percentile_uses_default_method() {
+        let engine = SortedPercentiles::new(vec![1.0, 2.0, 2.0, 3.0]);
+        assert_eq!(
+            engine.percentile(2.0),
+            engine.percentile_with(2.0, PercentileMethod::default())
+        );
+        assert_eq!(engine.percentile(2.0), Some(50.0));
+    }
+
+    #[test]
+    fn interpolated_is_continuous_between_values() {
+        let engine = SortedPercentiles::new(vec![100.0, 200.0]);
+        let p = |v| {
+            engine
+                .percentile_with(v, PercentileMethod::Interpolated)
+                .unwrap()
+        };
+        // Mid-ranks are 25% at 100 and 75% at 200.
+        assert_eq!(p(150.0), 50.0);
+        assert_eq!(p(125.0), 37.5);
+        assert!(p(199.999) < p(200.0));
+    }
+
+    #[test]
+    fn histogram_interpolates_within_bin() {
+        let engine = HistogramPercentiles::new(100.0, 10.0, vec![2, 4, 2, 2]);
+        let p = |v| engine.percentile_with(v, PercentileMethod::Interpolated);
+        assert_eq!(p(115.0), Some(40.0));
+        assert_eq!(p(110.0), Some(20.0));
+        assert_eq!(
+            engine.percentile_with(115.0, PercentileMethod::Exact),
+            Some(20.0)
+        );
+    }
+
+    #[test]
+    fn display_percentile_rounds_to_one_decimal() {
+        assert_eq!(round_percentile(83.3333), 83.3);
+        assert_eq!(round_percentile(99.96), 100.0);
+        let engine = SortedPercentiles::new(vec![1.0, 2.0, 3.0]);
+        assert_eq!(
+            engine.display_percentile(2.0, PercentileMethod::Interpolated),
+            Some(50.0)
+        );
+        assert_eq!(
+            engine.display_percentile(1.0, PercentileMethod::Interpolated),
+            Some(16.7)
+        );
+    }
+
+    #[test]
+    fn sketch_tracks_exact_percentiles() {
+        let exact = SortedPercentiles::new(sample_values());
+        let sketch = SketchPercentiles::from_sorted(&exact, DEFAULT_SKETCH_POINTS);
+        assert_eq!(sketch.population(), 2_000);
+
+        for value in [110.0, 150.0, 199.5, 240.0, 290.0] {
+            let want = exact
+                .percentile_with(value, PercentileMethod::Interpolated)
+                .unwrap();
+            let got = sketch
+                .percentile_with(value, PercentileMethod::Interpolated)
+                .unwrap();
+            assert!(
+                (want - got).abs() < 1.0,
+                "{value}: exact {want}, sketch {got}"
+            );
+        }
+        assert_eq!(sketch.percentile(0.0), Some(0.0));
+        assert_eq!(sketch.percentile(1_000.0), Some(100.0));
+    }
+
+    #[test]
+    fn sketch_survives_binary_roundtrip() {
+        let exact = SortedPercentiles::new(sample_values());
+        let sketch = SketchPercentiles::from_sorted(&exact, DEFAULT_SKETCH_POINTS);
+        let (header, quantiles) = sketch.to_header(1);
+        let bytes = encode_quantile_sketch(header, &quantiles).unwrap();
+        assert!(bytes.len() < 500);
+
+        let (header, quantiles) = decode_quantile_sketch(&bytes).unwrap();
+        let decoded = SketchPercentiles::from_header(&header, quantiles);
+        let value = 210.0;
+        let diff = decoded
+            .percentile_with(value, PercentileMethod::Interpolated)
+            .unwrap()
+            - sketch
+                .percentile_with(value, PercentileMethod::Interpolated)
+                .unwrap();
+        assert!(diff.abs() < 1e-3);
+    }
+
+    #[test]
+    fn sketch_handles_ties_and_empty_input() {
+        let sketch = SketchPercentiles::new(vec![1.0, 2.0, 2.0, 2.0, 3.0], 100);
+        assert_eq!(
+            sketch.percentile_with(2.0, PercentileMethod::Exact),
+            Some(25.0)
+        );
+        assert_eq!(
+            sketch.percentile_with(2.0, PercentileMethod::Interpolated),
+            Some(50.0)
+        );
+
+        let empty = SketchPercentiles::from_sorted(&SortedPercentiles::new(Vec::new()), 101);
+        assert_eq!(empty.percentile(1.0), None);
+    }
+
+    #[test]
+    fn cache_builds_once_per_signature() {
+        let mut cache = PercentileCache::new("v2026-04-19");
+        let mut loads = 0;
+        for _ in 0..3 {
+            let engine = cache.get_or_build("sex=M|lift=T", || {
+                loads += 1;
+                vec![300.0, 100.0, 200.0]
+            });
+            assert_eq!(engine.percentile(250.0), Some(200.0 / 3.0));
+        }
+        assert_eq!(loads, 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.stats().hits, 2);
+        assert_eq!(cache.stats().misses, 1);
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_within_budget() {
+        // Room for two three-value populations.
+        let mut cache = PercentileCache::with_max_bytes("v1", 48);
+        let values = || vec![1.0, 2.0, 3.0];
+        cache.get_or_build("a", values);
+        cache.get_or_build("b", values);
+        cache.get_or_build("a", values);
+        cache.get_or_build("c", values);
+
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.bytes(), 48);
+        assert_eq!(cache.stats().evictions, 1);
+
+        let mut reloaded = false;
+        cache.get_or_build("b", || {
+            reloaded = true;
+            values()
+        });
+        assert!(reloaded, "b should have been evicted before a");
+
+        let big = cache.get_or_build("big", || vec![0.0; 100]);
+        assert_eq!(big.population(), 100);
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn cache_rebuilds_expired_entries() {
+        let mut cache = PercentileCache::new("v1").with_ttl(Duration::from_secs(60));
+        let start = Instant::now();
+        let mut loads = 0;
+        let mut load_at = |cache: &mut PercentileCache<&str>, secs| {
+            cache.get_or_build_at("sex=M|lift=T", start + Duration::from_secs(secs), || {
+                loads += 1;
+                vec![1.0, 2.0]
+            });
+        };
+        load_at(&mut cache, 0);
+        load_at(&mut cache, 59);
+        load_at(&mut cache, 60);
+        assert_eq!(loads, 2);
+        assert_eq!(cache.stats().expirations, 1);
+        assert_eq!(cache.stats().hits, 1);
+
+        cache.get_or_build_at("sex=F|lift=T", start + Duration::from_secs(100), Vec::new);
+        assert_eq!(cache.purge_expired_at(start + Duration::from_secs(125)), 1);
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.bytes(), 0);
+    }
+
+    #[test]
+    fn cache_clears_on_new_data_version() {
+        let mut cache = PercentileCache::new("v1");
+        cache.get_or_build("sex=F|lift=B", || vec![50.0]);
+
+        assert!(!cache.set_data_version("v1"));
+        assert_eq!(cache.len(), 1);
+        assert!(cache.set_data_version("v2"));
+        assert!(cache.is_empty());
+        assert_eq!(cache.data_version(), "v2");
+    }
+
+    #[test]
+    fn confidence_flags_small_populations() {
+        let tiny = percentile_confidence(50.0, 8, DEFAULT_MIN_SAMPLE);
+        assert!(tiny.small_sample);
+        assert!(tiny.wide_interval);
+        assert!(!tiny.is_reliable());
+
+        let large = percentile_confidence(50.0, 10_000, DEFAULT_MIN_SAMPLE);
+        assert!(large.is_reliable());
+        assert!(large.ci_low > 48.0 && large.ci_high < 52.0);
+    }
+
+    #[test]
+    fn confidence_interval_stays_in_range() {
+        for percentile in [0.0, 0.5, 99.5, 100.0] {
+            let c = percentile_confidence(percentile, 40, DEFAULT_MIN_SAMPLE);
+            assert!(c.ci_low >= 0.0 && c.ci_high <= 100.0);
+            assert!(c.ci_low <= percentile && percentile <= c.ci_high);
+        }
+        let empty = percentile_confidence(50.0, 0, DEFAULT_MIN_SAMPLE);
+        assert_eq!((empty.ci_low, empty.ci_high), (0.0, 100.0));
+    }
+
+    #[test]
+    fn confidence_for_non_finite_percentile_is_unreliable() {
+        for percentile in [f64::NAN, f64::INFINITY] {
+            let c = percentile_confidence(percentile, 10_000, DEFAULT_MIN_SAMPLE);
+            assert_eq!((c.ci_low, c.ci_high), (0.0, 100.0));
+            assert!(c.wide_interval);
+            assert!(!c.is_reliable());
+        }
+    }
+}
@@ -45,9 +45,48 @@ pub fn rebin_2d(
     (out, w2, h2)
 }
 
+/// Smallest chunk size `k` for which `rebin_1d(counts, k)` yields at most `target_bins` bins.
+///
+/// Used to honour a requested bin count (e.g. 30/50/100) on top of the published base bins.
+pub fn factor_for_bin_count(bins: usize, target_bins: usize) -> usize {
+    assert!(target_bins > 0, "target_bins must be > 0");
+    bins.div_ceil(target_bins).max(1)
+}
+
+/// Crops a 1D histogram to the base bins overlapping `[x_min, x_max]`.
+///
+/// `min` is the lower edge of bin 0 and `bin_size` the base bin width. Returns the cropped
+/// counts with their new lower and upper edges, or `None` when the range is empty, inverted,
+/// NaN, or entirely outside the histogram. An infinite bound on either side is clamped to
+/// the histogram edge.
+pub fn crop_1d(
+    counts: &[u32],
+    min: f32,
+    bin_size: f32,
+    x_min: f32,
+    x_max: f32,
+) -> Option<(Vec<u32>, f32, f32)> {
+    if counts.is_empty() || bin_size <= 0.0 || x_min.is_nan() || x_max.is_nan() || x_max <= x_min {
+        return None;
+    }
+
+    let first = ((x_min - min) / bin_size).floor().max(0.0) as usize;
+    let last = (((x_max - min) / bin_size).ceil() as isize).min(counts.len() as isize);
+    if last <= first as isize {
+        return None;
+    }
+    let last = last as usize;
+
+    Some((
+        counts[first..last].to_vec(),
+        min + first as f32 * bin_size,
+        min + last as f32 * bin_size,
+    ))
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{rebin_1d, rebin_2d};
+    use super::{crop_1d, factor_for_bin_count, rebin_1d, rebin_2d};
 
     #[test]
     fn rebin_1d_groups_chunks() {
@@ -83,4 +122,57 @@ mod tests {
         assert_eq!((w2, h2), (2, 2));
         assert_eq!(out, vec![12, 9, 15, 9]);
     }
+
+    #[test]
+    fn factor_for_bin_count_caps_output_bins() {
+        assert_eq!(factor_for_bin_count(120, 30), 4);
+        assert_eq!(factor_for_bin_count(120, 50), 3);
+        assert_eq!(factor_for_bin_count(120, 200), 1);
+        assert_eq!(
+            rebin_1d(vec![1; 120], factor_for_bin_count(120, 50)).len(),
+            40
+        );
+    }
+
+    #[test]
+    fn crop_1d_keeps_overlapping_bins() {
+        // Bins of width 10 starting at 100: [100,110) [110,120) [120,130) [130,140)
+        let counts = [1, 2, 3, 4];
+        let (cropped, lo, hi) = crop_1d(&counts, 100.0, 10.0, 115.0, 125.0).expect("overlap");
+        assert_eq!(cropped, vec![2, 3]);
+        assert_eq!((lo, hi), (110.0, 130.0));
+
+        let (cropped, lo, hi) = crop_1d(&counts, 100.0, 10.0, 0.0, 1000.0).expect("overlap");
+        assert_eq!(cropped, counts.to_vec());
+        assert_eq!((lo, hi), (100.0, 140.0));
+    }
+
+    #[test]
+    fn crop_1d_rejects_empty_ranges() {
+        let counts = [1, 2, 3, 4];
+        assert_eq!(crop_1d(&counts, 100.0, 10.0, 130.0, 120.0), None);
+        assert_eq!(crop_1d(&counts, 100.0, 10.0, 200.0, 300.0), None);
+        assert_eq!(crop_1d(&counts, 100.0, 10.0, 0.0, 50.0), None);
+        assert_eq!(crop_1d(&counts, 100.0, 10.0, f32::NAN, 120.0), None);
+        assert_eq!(crop_1d(&counts, 100.0, 10.0, 110.0, f32::NAN), None);
+        assert_eq!(
+            crop_1d(&counts, 100.0, 10.0, f32::INFINITY, f32::INFINITY),
+            None
+        );
+        assert_eq!(
+            crop_1d(&counts, 100.0, 10.0, f32::NEG_INFINITY, f32::NEG_INFINITY),
+            None
+        );
+    }
+
+    #[test]
+    fn crop_1d_clamps_infinite_bounds_to_histogram_edges() {
+        let counts = [1, 2, 3, 4];
+        let open_low = crop_1d(&counts, 100.0, 10.0, f32::NEG_INFINITY, 115.0);
+        assert_eq!(open_low, Some((vec![1, 2], 100.0, 120.0)));
+        let open_high = crop_1d(&counts, 100.0, 10.0, 125.0, f32::INFINITY);
+        assert_eq!(open_high, Some((vec![3, 4], 120.0, 140.0)));
+        let open = crop_1d(&counts, 100.0, 10.0, f32::NEG_INFINITY, f32::INFINITY);
+        assert_eq!(open, Some((counts.to_vec(), 100.0, 140.0)));
+    }
 }
@@ -0,0 +1,226 @@
+pub mod constants;
+#[cfg(test)]
+mod snapshot;
+
+use crate::model::{Equipment, LiftType, Sex};
+use constants::{
+    DOTS_FEMALE, DOTS_MALE, DOTS_MAX_BW_FEMALE, DOTS_MAX_BW_MALE, DOTS_MIN_BW, GL_FEMALE_EQ_B,
+    GL_FEMALE_EQ_SBD, GL_FEMALE_RAW_B, GL_FEMALE_RAW_SBD, GL_MALE_EQ_B, GL_MALE_EQ_SBD,
+    GL_MALE_RAW_B, GL_MALE_RAW_SBD, GL_MIN_BW, WILKS_BW_RANGE_FEMALE, WILKS_BW_RANGE_MALE,
+    WILKS_FEMALE, WILKS_MALE,
+};
+
+/// Bodyweight-adjusted scoring formulas.
+///
+/// Every formula is linear in the lifted weight (`score = lift * coefficient(bw)`),
+/// so the inverse has a closed form and needs no numeric solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Formula {
+    Dots,
+    Wilks,
+    /// IPF GL points (2020 revision).
+    IpfGl {
+        equipment: Equipment,
+        event: GlEvent,
+    },
+}
+
+/// Events IPF GL publishes coefficients for. There are no single-lift squat or deadlift
+/// tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GlEvent {
+    /// Full-power total (SBD).
+    Total,
+    /// Bench-only competition.
+    Bench,
+}
+
+impl GlEvent {
+    /// The GL event scoring `lift`, or `None` for squat and deadlift.
+    pub fn for_lift(lift: LiftType) -> Option<Self> {
+        match lift {
+            LiftType::Total => Some(GlEvent::Total),
+            LiftType::Bench => Some(GlEvent::Bench),
+            LiftType::Squat | LiftType::Deadlift => None,
+        }
+    }
+}
+
+fn polynomial(coefficients: &[f64], x: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |acc, c| acc * x + c)
+}
+
+/// DOTS multiplier for a bodyweight in kg.
+///
+/// Bodyweight is clamped to the formula's fitted range (40-210 kg men, 40-150 kg women).
+pub fn dots_coefficient(bodyweight: f64, sex: Sex) -> f64 {
+    let (coefficients, max_bw) = match sex {
+        Sex::Male => (&DOTS_MALE, DOTS_MAX_BW_MALE),
+        Sex::Female => (&DOTS_FEMALE, DOTS_MAX_BW_FEMALE),
+    };
+    let bw = bodyweight.clamp(DOTS_MIN_BW, max_bw);
+    500.0 / polynomial(coefficients, bw)
+}
+
+/// Wilks multiplier for a bodyweight in kg.
+///
+/// Bodyweight is clamped the same way OpenPowerlifting does for the original formula.
+pub fn wilks_coefficient(bodyweight: f64, sex: Sex) -> f64 {
+    let (coefficients, (min_bw, max_bw)) = match sex {
+        Sex::Male => (&WILKS_MALE, WILKS_BW_RANGE_MALE),
+        Sex::Female => (&WILKS_FEMALE, WILKS_BW_RANGE_FEMALE),
+    };
+    let bw = bodyweight.clamp(min_bw, max_bw);
+    500.0 / polynomial(coefficients, bw)
+}
+
+/// IPF GL multiplier for a bodyweight in kg.
+///
+/// Raw, wraps, and straps use the classic table. Returns `0.0` below 35 kg, where the
+/// formula is undefined.
+pub fn ipf_gl_coefficient(bodyweight: f64, sex: Sex, equipment: Equipment, event: GlEvent) -> f64 {
+    if bodyweight < GL_MIN_BW {
+        return 0.0;
+    }
+    let bench_only = event == GlEvent::Bench;
+    let [a, b, c] = match (sex, equipment.is_equipped(), bench_only) {
+        (Sex::Male, false, false) => GL_MALE_RAW_SBD,
+        (Sex::Male, true, false) => GL_MALE_EQ_SBD,
+        (Sex::Female, false, false) => GL_FEMALE_RAW_SBD,
+        (Sex::Female, true, false) => GL_FEMALE_EQ_SBD,
+        (Sex::Male, false, true) => GL_MALE_RAW_B,
+        (Sex::Male, true, true) => GL_MALE_EQ_B,
+        (Sex::Female, false, true) => GL_FEMALE_RAW_B,
+        (Sex::Female, true, true) => GL_FEMALE_EQ_B,
+    };
+    let denominator = a - b * (-c * bodyweight).exp();
+    if denominator <= 0.0 {
+        return 0.0;
+    }
+    100.0 / denominator
+}
+
+impl Formula {
+    /// Bodyweight multiplier applied to the lifted weight.
+    pub fn coefficient(self, bodyweight: f64, sex: Sex) -> f64 {
+        match self {
+            Formula::Dots => dots_coefficient(bodyweight, sex),
+            Formula::Wilks => wilks_coefficient(bodyweight, sex),
+            Formula::IpfGl { equipment, event } => {
+                ipf_gl_coefficient(bodyweight, sex, equipment, event)
+            }
+        }
+    }
+
+    /// Score for `lift` kg at `bodyweight` kg.
+    pub fn score(self, lift: f64, bodyweight: f64, sex: Sex) -> f64 {
+        lift * self.coefficient(bodyweight, sex)
+    }
+
+    /// Lift in kg needed to reach `target_score` at `bodyweight` kg.
+    ///
+    /// Returns `None` when the target is negative or not finite, or when the formula
+    /// has no usable multiplier at this bodyweight.
+    pub fn lift_for_score(self, target_score: f64, bodyweight: f64, sex: Sex) -> Option<f64> {
+        if !target_score.is_finite() || target_score < 0.0 {
+            return None;
+        }
+        let coefficient = self.coefficient(bodyweight, sex);
+        if !coefficient.is_finite() || coefficient <= 0.0 {
+            return None;
+        }
+        Some(target_score / coefficient)
+    }
+}
+
+/// DOTS score for `lift` kg at `bodyweight` kg.
+pub fn dots(lift: f64, bodyweight: f64, sex: Sex) -> f64 {
+    Formula::Dots.score(lift, bodyweight, sex)
+}
+
+/// Wilks score for `lift` kg at `bodyweight` kg.
+pub fn wilks(lift: f64, bodyweight: f64, sex: Sex) -> f64 {
+    Formula::Wilks.score(lift, bodyweight, sex)
+}
+
+/// IPF GL points for `lift_kg` kg at `bodyweight` kg.
+pub fn ipf_gl(
+    lift_kg: f64,
+    bodyweight: f64,
+    sex: Sex,
+    equipment: Equipment,
+    event: GlEvent,
+) -> f64 {
+    Formula::IpfGl { equipment, event }.score(lift_kg, bodyweight, sex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Formula, GlEvent, dots, ipf_gl, wilks};
+    use crate::model::{Equipment, LiftType, Sex};
+
+    fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+        assert!(
+            (actual - expected).abs() <= tolerance,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn forward_scores_match_reference_values() {
+        assert_close(dots(700.0, 93.0, Sex::Male), 445.38, 0.01);
+        assert_close(wilks(700.0, 93.0, Sex::Male), 439.73, 0.01);
+        assert_close(
+            ipf_gl(700.0, 93.0, Sex::Male, Equipment::Raw, GlEvent::Total),
+            91.57,
+            0.01,
+        );
+        assert_close(dots(400.0, 63.0, Sex::Female), 430.21, 0.01);
+    }
+
+    #[test]
+    fn lift_for_score_inverts_score() {
+        let formulas = [
+            Formula::Dots,
+            Formula::Wilks,
+            Formula::IpfGl {
+                equipment: Equipment::Raw,
+                event: GlEvent::Total,
+            },
+            Formula::IpfGl {
+                equipment: Equipment::SinglePly,
+                event: GlEvent::Bench,
+            },
+        ];
+        for formula in formulas {
+            for sex in [Sex::Male, Sex::Female] {
+                let lift = formula
+                    .lift_for_score(450.0, 93.0, sex)
+                    .expect("inverse should exist");
+                assert_close(formula.score(lift, 93.0, sex), 450.0, 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn lift_for_score_rejects_unusable_inputs() {
+        assert_eq!(Formula::Dots.lift_for_score(-1.0, 93.0, Sex::Male), None);
+        assert_eq!(
+            Formula::Dots.lift_for_score(f64::NAN, 93.0, Sex::Male),
+            None
+        );
+        let gl = Formula::IpfGl {
+            equipment: Equipment::Raw,
+            event: GlEvent::Total,
+        };
+        assert_eq!(gl.lift_for_score(80.0, 30.0, Sex::Female), None);
+    }
+
+    #[test]
+    fn gl_has_no_single_lift_squat_or_deadlift_event() {
+        assert_eq!(GlEvent::for_lift(LiftType::Total), Some(GlEvent::Total));
+        assert_eq!(GlEvent::for_lift(LiftType::Bench), Some(GlEvent::Bench));
+        assert_eq!(GlEvent::for_lift(LiftType::Squat), None);
+        assert_eq!(GlEvent::for_lift(LiftType::Deadlift), None);
+    }
+}
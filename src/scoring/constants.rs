@@ -0,0 +1,88 @@
+//! Single home for the published scoring coefficients.
+//!
+//! Polynomial tables are ordered from the constant term upwards, so
+//! `coefficients[i]` multiplies `bodyweight^i`.
+
+/// DOTS denominator polynomial for men.
+pub const DOTS_MALE: [f64; 5] = [
+    -307.75076,
+    24.0900756,
+    -0.1918759221,
+    0.0007391293,
+    -0.000001093,
+];
+/// DOTS denominator polynomial for women.
+pub const DOTS_FEMALE: [f64; 5] = [
+    -57.96288,
+    13.6175032,
+    -0.1126655495,
+    0.0005158568,
+    -0.0000010706,
+];
+/// Bodyweight range (kg) the DOTS polynomial is evaluated over.
+pub const DOTS_MIN_BW: f64 = 40.0;
+pub const DOTS_MAX_BW_MALE: f64 = 210.0;
+pub const DOTS_MAX_BW_FEMALE: f64 = 150.0;
+
+/// Original Wilks denominator polynomial for men.
+pub const WILKS_MALE: [f64; 6] = [
+    -216.0475144,
+    16.2606339,
+    -0.002388645,
+    -0.00113732,
+    7.01863e-06,
+    -1.291e-08,
+];
+/// Original Wilks denominator polynomial for women.
+pub const WILKS_FEMALE: [f64; 6] = [
+    594.31747775582,
+    -27.23842536447,
+    0.82112226871,
+    -0.00930733913,
+    4.731582e-05,
+    -9.054e-08,
+];
+/// Bodyweight clamps (kg) OpenPowerlifting applies to the original Wilks formula.
+pub const WILKS_BW_RANGE_MALE: (f64, f64) = (40.0, 201.9);
+pub const WILKS_BW_RANGE_FEMALE: (f64, f64) = (26.51, 154.53);
+
+/// `[A, B, C]` parameters for IPF GL, split by sex, classic/equipped, and SBD/bench-only.
+pub const GL_MALE_RAW_SBD: [f64; 3] = [1199.72839, 1025.18162, 0.00921];
+pub const GL_MALE_EQ_SBD: [f64; 3] = [1236.25115, 1449.21864, 0.01644];
+pub const GL_FEMALE_RAW_SBD: [f64; 3] = [610.32796, 1045.59282, 0.03048];
+pub const GL_FEMALE_EQ_SBD: [f64; 3] = [758.63878, 949.31382, 0.02435];
+pub const GL_MALE_RAW_B: [f64; 3] = [320.98041, 281.40258, 0.01008];
+pub const GL_MALE_EQ_B: [f64; 3] = [381.22073, 733.79378, 0.02398];
+pub const GL_FEMALE_RAW_B: [f64; 3] = [142.40398, 442.52671, 0.04724];
+pub const GL_FEMALE_EQ_B: [f64; 3] = [221.82209, 357.00377, 0.02937];
+/// Bodyweight (kg) below which IPF GL is undefined.
+pub const GL_MIN_BW: f64 = 35.0;
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{Equipment, Sex};
+    use crate::scoring::{GlEvent, dots, ipf_gl, wilks};
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= 1e-4,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn hundred_kilos_at_reference_bodyweights() {
+        assert_close(dots(100.0, 93.0, Sex::Male), 63.6251);
+        assert_close(dots(100.0, 63.0, Sex::Female), 107.5515);
+        assert_close(wilks(100.0, 93.0, Sex::Male), 62.8190);
+        assert_close(wilks(100.0, 63.0, Sex::Female), 107.3957);
+        assert_close(
+            ipf_gl(100.0, 93.0, Sex::Male, Equipment::Raw, GlEvent::Total),
+            13.0821,
+        );
+        assert_close(
+            ipf_gl(100.0, 63.0, Sex::Female, Equipment::Raw, GlEvent::Total),
+            21.8783,
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! Regression snapshot and property tests for the scoring formulas.
+//!
+//! `snapshot.csv` holds 100 entries spanning both sexes, every equipment class, totals and
+//! bench-only results, plus bodyweights outside the clamp ranges. Its expected values were
+//! produced by this crate's own formulas and rounded to three decimals, so they catch
+//! unintended changes in output but do not independently verify the coefficients.
+
+use crate::model::{Equipment, LiftType, Sex};
+use crate::scoring::{Formula, GlEvent, dots, ipf_gl, wilks};
+
+const SNAPSHOT: &str = include_str!("snapshot.csv");
+const TOLERANCE: f64 = 0.0015;
+
+struct SnapshotRow {
+    sex: Sex,
+    bodyweight: f64,
+    equipment: Equipment,
+    lift: LiftType,
+    kg: f64,
+    dots: f64,
+    wilks: f64,
+    gl: f64,
+}
+
+fn snapshot_rows() -> Vec<SnapshotRow> {
+    SNAPSHOT
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields: Vec<&str> = line.split(',').collect();
+            assert_eq!(fields.len(), 8, "malformed snapshot row: {line}");
+            let number = |i: usize| -> f64 { fields[i].parse().expect("numeric snapshot field") };
+            SnapshotRow {
+                sex: fields[0].parse().expect("snapshot sex"),
+                bodyweight: number(1),
+                equipment: fields[2].parse().expect("snapshot equipment"),
+                lift: fields[3].parse().expect("snapshot lift"),
+                kg: number(4),
+                dots: number(5),
+                wilks: number(6),
+                gl: number(7),
+            }
+        })
+        .collect()
+}
+
+fn check(label: &str, row: &SnapshotRow, actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() <= TOLERANCE,
+        "{label} mismatch for {:?} {} kg @ {} kg {:?} {:?}: expected {expected}, got {actual}",
+        row.sex,
+        row.kg,
+        row.bodyweight,
+        row.equipment,
+        row.lift,
+    );
+}
+
+#[test]
+fn snapshot_fixture_covers_expected_cases() {
+    let rows = snapshot_rows();
+    assert_eq!(rows.len(), 100);
+    assert!(rows.iter().any(|row| row.sex == Sex::Female));
+    assert!(rows.iter().any(|row| row.lift == LiftType::Bench));
+    assert!(rows.iter().any(|row| row.equipment.is_equipped()));
+    assert!(rows.iter().any(|row| row.bodyweight > 210.0));
+}
+
+#[test]
+fn scores_match_snapshot_values() {
+    for row in snapshot_rows() {
+        check(
+            "DOTS",
+            &row,
+            dots(row.kg, row.bodyweight, row.sex),
+            row.dots,
+        );
+        check(
+            "Wilks",
+            &row,
+            wilks(row.kg, row.bodyweight, row.sex),
+            row.wilks,
+        );
+        check(
+            "IPF GL",
+            &row,
+            ipf_gl(
+                row.kg,
+                row.bodyweight,
+                row.sex,
+                row.equipment,
+                GlEvent::for_lift(row.lift).expect("snapshot rows are totals or bench"),
+            ),
+            row.gl,
+        );
+    }
+}
+
+fn all_formulas() -> Vec<Formula> {
+    let mut formulas = vec![Formula::Dots, Formula::Wilks];
+    for equipment in [Equipment::Raw, Equipment::SinglePly] {
+        for event in [GlEvent::Total, GlEvent::Bench] {
+            formulas.push(Formula::IpfGl { equipment, event });
+        }
+    }
+    formulas
+}
+
+#[test]
+fn scores_are_monotonic_in_lift() {
+    for formula in all_formulas() {
+        for sex in Sex::ALL {
+            for bodyweight in [45.0, 60.0, 83.0, 105.0, 140.0] {
+                let mut previous = 0.0;
+                for step in 1..=40 {
+                    let score = formula.score(step as f64 * 25.0, bodyweight, sex);
+                    assert!(
+                        score > previous,
+                        "{formula:?} {sex:?} not increasing at {bodyweight} kg"
+                    );
+                    previous = score;
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn coefficients_are_smooth_in_bodyweight() {
+    for formula in all_formulas() {
+        for sex in Sex::ALL {
+            let mut bodyweight: f64 = 40.0;
+            while bodyweight < 200.0 {
+                let here = formula.coefficient(bodyweight, sex);
+                let next = formula.coefficient(bodyweight + 0.1, sex);
+                assert!(here.is_finite() && here > 0.0);
+                assert!(
+                    (next - here).abs() / here < 0.01,
+                    "{formula:?} {sex:?} jumps between {bodyweight} and {} kg",
+                    bodyweight + 0.1
+                );
+                bodyweight += 0.1;
+            }
+        }
+    }
+}
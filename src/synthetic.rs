@@ -0,0 +1,251 @@
+//! Seeded synthetic lifter data for development and tests without the full dataset.
+//!
+//! Records use the same shape as `import` output. Bodyweight and strength are drawn
+//! independently per sex, then the total is derived from a DOTS target so heavier lifters
+//! lift more in the way real data does. Each record is assigned an equipment division
+//! drawn from configurable weights.
+
+use crate::import::ImportedLifter;
+use crate::model::{Equipment, Sex};
+use crate::scoring::Formula;
+
+/// Shape of one sex's population: means and standard deviations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PopulationShape {
+    pub bodyweight_mean: f64,
+    pub bodyweight_sd: f64,
+    pub dots_mean: f64,
+    pub dots_sd: f64,
+    /// Average squat / bench / deadlift share of the total; should sum to 1.
+    pub lift_split: [f64; 3],
+}
+
+impl PopulationShape {
+    pub const MALE: Self = Self {
+        bodyweight_mean: 90.0,
+        bodyweight_sd: 17.0,
+        dots_mean: 330.0,
+        dots_sd: 70.0,
+        lift_split: [0.36, 0.25, 0.39],
+    };
+    pub const FEMALE: Self = Self {
+        bodyweight_mean: 68.0,
+        bodyweight_sd: 13.0,
+        dots_mean: 310.0,
+        dots_sd: 65.0,
+        lift_split: [0.37, 0.21, 0.42],
+    };
+}
+
+/// Generator settings. The same config always produces the same records.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    pub count: usize,
+    pub seed: u64,
+    /// Fraction of records that are female, 0-1.
+    pub female_share: f64,
+    /// Relative weight of each division in [`Equipment::ALL`] order; all zero leaves
+    /// equipment unset.
+    pub equipment_weights: [f64; 6],
+    pub male: PopulationShape,
+    pub female: PopulationShape,
+}
+
+impl GeneratorConfig {
+    /// Mostly raw, with a minority of wraps and equipped lifters.
+    pub const DEFAULT_EQUIPMENT_WEIGHTS: [f64; 6] = [0.75, 0.08, 0.1, 0.02, 0.01, 0.04];
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            count: 1_000,
+            seed: 0,
+            female_share: 0.3,
+            equipment_weights: Self::DEFAULT_EQUIPMENT_WEIGHTS,
+            male: PopulationShape::MALE,
+            female: PopulationShape::FEMALE,
+        }
+    }
+}
+
+/// SplitMix64: small, fast, and stable across platforms and releases.
+#[derive(Debug, Clone)]
+pub struct SeededRng(u64);
+
+impl SeededRng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Normal sample via Box-Muller.
+    pub fn normal(&mut self, mean: f64, sd: f64) -> f64 {
+        let u1 = 1.0 - self.next_f64();
+        let u2 = self.next_f64();
+        mean + sd * (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+}
+
+fn round_to_plate(kg: f64) -> f64 {
+    (kg / 2.5).round() * 2.5
+}
+
+/// Draws a division in proportion to `weights`, or `None` if they are all zero.
+fn pick_equipment(rng: &mut SeededRng, weights: &[f64; 6]) -> Option<Equipment> {
+    let sum: f64 = weights.iter().sum();
+    if sum.is_nan() || sum <= 0.0 {
+        return None;
+    }
+    let mut pick = rng.next_f64() * sum;
+    let mut chosen = None;
+    for (equipment, &weight) in Equipment::ALL.iter().zip(weights) {
+        if weight > 0.0 {
+            chosen = Some(*equipment);
+            if pick < weight {
+                break;
+            }
+            pick -= weight;
+        }
+    }
+    chosen
+}
+
+fn generate_one(rng: &mut SeededRng, index: usize, config: &GeneratorConfig) -> ImportedLifter {
+    let sex = if rng.next_f64() < config.female_share {
+        Sex::Female
+    } else {
+        Sex::Male
+    };
+    let equipment = pick_equipment(rng, &config.equipment_weights);
+    let shape = match sex {
+        Sex::Male => &config.male,
+        Sex::Female => &config.female,
+    };
+
+    let bodyweight_kg = (rng
+        .normal(shape.bodyweight_mean, shape.bodyweight_sd)
+        .clamp(40.0, 200.0)
+        * 10.0)
+        .round()
+        / 10.0;
+    let target_dots = rng
+        .normal(shape.dots_mean, shape.dots_sd)
+        .clamp(shape.dots_mean * 0.3, shape.dots_mean * 2.0);
+    let total = Formula::Dots
+        .lift_for_score(target_dots, bodyweight_kg, sex)
+        .unwrap_or(0.0);
+
+    let [squat, bench, deadlift] = shape
+        .lift_split
+        .map(|share| round_to_plate(total * share * rng.normal(1.0, 0.05).clamp(0.85, 1.15)));
+
+    ImportedLifter {
+        name: format!("Lifter {:06}", index + 1),
+        sex,
+        bodyweight_kg,
+        squat_kg: Some(squat),
+        bench_kg: Some(bench),
+        deadlift_kg: Some(deadlift),
+        total_kg: Some(squat + bench + deadlift),
+        reported_dots: None,
+        equipment,
+    }
+}
+
+/// Generates `config.count` lifters deterministically from `config.seed`.
+pub fn generate(config: &GeneratorConfig) -> Vec<ImportedLifter> {
+    let mut rng = SeededRng::new(config.seed);
+    (0..config.count)
+        .map(|index| generate_one(&mut rng, index, config))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{GeneratorConfig, generate};
+    use crate::model::{Equipment, Sex};
+    use crate::scoring::dots;
+
+    #[test]
+    fn same_seed_same_records() {
+        let config = GeneratorConfig {
+            count: 50,
+            seed: 7,
+            ..GeneratorConfig::default()
+        };
+        assert_eq!(generate(&config), generate(&config));
+        assert_ne!(
+            generate(&config),
+            generate(&GeneratorConfig { seed: 8, ..config })
+        );
+    }
+
+    #[test]
+    fn records_are_plausible() {
+        let config = GeneratorConfig {
+            count: 2_000,
+            seed: 1,
+            female_share: 0.4,
+            ..GeneratorConfig::default()
+        };
+        let lifters = generate(&config);
+        let female = lifters.iter().filter(|l| l.sex == Sex::Female).count() as f64;
+        assert!((female / 2_000.0 - 0.4).abs() < 0.05);
+
+        let mean_dots = lifters
+            .iter()
+            .map(|l| dots(l.total_kg.unwrap(), l.bodyweight_kg, l.sex))
+            .sum::<f64>()
+            / 2_000.0;
+        assert!((280.0..360.0).contains(&mean_dots), "mean DOTS {mean_dots}");
+
+        for lifter in &lifters {
+            assert!((40.0..=200.0).contains(&lifter.bodyweight_kg));
+            assert!(lifter.bench_kg.unwrap() < lifter.deadlift_kg.unwrap());
+        }
+    }
+
+    #[test]
+    fn equipment_follows_weights() {
+        let config = GeneratorConfig {
+            count: 2_000,
+            seed: 3,
+            ..GeneratorConfig::default()
+        };
+        let lifters = generate(&config);
+        let raw = lifters
+            .iter()
+            .filter(|l| l.equipment == Some(Equipment::Raw))
+            .count() as f64;
+        assert!((raw / 2_000.0 - 0.75).abs() < 0.05, "raw share {raw}");
+        assert!(lifters.iter().all(|l| l.equipment.is_some()));
+
+        let single_ply = GeneratorConfig {
+            equipment_weights: [0.0, 0.0, 1.0, 0.0, 0.0, 0.0],
+            ..config.clone()
+        };
+        assert!(
+            generate(&single_ply)
+                .iter()
+                .all(|l| l.equipment == Some(Equipment::SinglePly))
+        );
+        let unset = GeneratorConfig {
+            equipment_weights: [0.0; 6],
+            ..config
+        };
+        assert!(generate(&unset).iter().all(|l| l.equipment.is_none()));
+    }
+}
@@ -0,0 +1,250 @@
+//! Streaming t-digest for building quantile sketches without holding every value.
+//!
+//! Values are buffered and periodically merged into weighted centroids using the `k1`
+//! (arcsine) scale function, which keeps centroids small near the tails where percentile
+//! lookups need the most precision. Digests built in parallel can be merged.
+
+use crate::percentile::SketchPercentiles;
+use std::f64::consts::PI;
+
+/// Default compression: at most roughly this many centroids are kept.
+pub const DEFAULT_COMPRESSION: f64 = 100.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Centroid {
+    mean: f64,
+    weight: f64,
+}
+
+/// Mergeable t-digest over `f64` values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TDigest {
+    compression: f64,
+    centroids: Vec<Centroid>,
+    buffer: Vec<f64>,
+    count: u64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for TDigest {
+    fn default() -> Self {
+        Self::new(DEFAULT_COMPRESSION)
+    }
+}
+
+impl TDigest {
+    pub fn new(compression: f64) -> Self {
+        assert!(compression >= 10.0, "compression must be >= 10");
+        Self {
+            compression,
+            centroids: Vec::new(),
+            buffer: Vec::new(),
+            count: 0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+        }
+    }
+
+    /// Number of values added, ignoring non-finite ones.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Adds one value. Non-finite values are skipped.
+    pub fn add(&mut self, value: f64) {
+        if !value.is_finite() {
+            return;
+        }
+        self.buffer.push(value);
+        self.count += 1;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        if self.buffer.len() >= (self.compression * 5.0) as usize {
+            self.compress();
+        }
+    }
+
+    /// Folds `other` into this digest.
+    pub fn merge(&mut self, other: &TDigest) {
+        self.compress();
+        let mut other = other.clone();
+        other.compress();
+        self.centroids.extend(other.centroids);
+        self.count += other.count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+        self.merge_centroids();
+    }
+
+    fn compress(&mut self) {
+        if self.buffer.is_empty() {
+            return;
+        }
+        self.centroids.extend(
+            self.buffer
+                .drain(..)
+                .map(|mean| Centroid { mean, weight: 1.0 }),
+        );
+        self.merge_centroids();
+    }
+
+    fn scale(&self, q: f64) -> f64 {
+        self.compression / (2.0 * PI) * (2.0 * q - 1.0).clamp(-1.0, 1.0).asin()
+    }
+
+    fn merge_centroids(&mut self) {
+        if self.centroids.is_empty() {
+            return;
+        }
+        self.centroids.sort_by(|a, b| a.mean.total_cmp(&b.mean));
+        let total: f64 = self.centroids.iter().map(|c| c.weight).sum();
+
+        let mut merged: Vec<Centroid> = Vec::with_capacity(self.centroids.len());
+        let mut current = self.centroids[0];
+        let mut weight_before = 0.0;
+        let mut k_left = self.scale(0.0);
+        for next in self.centroids[1..].iter().copied() {
+            let q_right = (weight_before + current.weight + next.weight) / total;
+            if self.scale(q_right) - k_left <= 1.0 {
+                let weight = current.weight + next.weight;
+                current.mean += (next.mean - current.mean) * next.weight / weight;
+                current.weight = weight;
+            } else {
+                weight_before += current.weight;
+                k_left = self.scale(weight_before / total);
+                merged.push(current);
+                current = next;
+            }
+        }
+        merged.push(current);
+        self.centroids = merged;
+    }
+
+    /// Estimated value at quantile `q` (0-1), or `None` if the digest is empty.
+    pub fn quantile(&mut self, q: f64) -> Option<f64> {
+        self.compress();
+        if self.centroids.is_empty() {
+            return None;
+        }
+        if q <= 0.0 {
+            return Some(self.min);
+        }
+        if q >= 1.0 {
+            return Some(self.max);
+        }
+
+        let total = self.count as f64;
+        let target = q * total;
+        // Interpolate between centroid centres, anchored at min (rank 0) and max (rank n).
+        let mut prev_rank = 0.0;
+        let mut prev_value = self.min;
+        let mut cumulative = 0.0;
+        for centroid in &self.centroids {
+            let centre = cumulative + centroid.weight / 2.0;
+            if target < centre {
+                let t = (target - prev_rank) / (centre - prev_rank);
+                return Some(prev_value + (centroid.mean - prev_value) * t);
+            }
+            prev_rank = centre;
+            prev_value = centroid.mean;
+            cumulative += centroid.weight;
+        }
+        let t = (target - prev_rank) / (total - prev_rank);
+        Some(prev_value + (self.max - prev_value) * t.clamp(0.0, 1.0))
+    }
+
+    /// Evenly spaced quantiles from the minimum to the maximum, as a queryable sketch.
+    pub fn to_sketch(&mut self, points: usize) -> SketchPercentiles {
+        if self.count == 0 || points < 2 {
+            return SketchPercentiles::new(Vec::new(), self.count);
+        }
+        let mut quantiles: Vec<f64> = (0..points)
+            .filter_map(|i| self.quantile(i as f64 / (points - 1) as f64))
+            .collect();
+        // Guard against float noise breaking monotonicity between adjacent centroids.
+        for i in 1..quantiles.len() {
+            quantiles[i] = quantiles[i].max(quantiles[i - 1]);
+        }
+        SketchPercentiles::new(quantiles, self.count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TDigest;
+    use crate::percentile::{
+        DEFAULT_SKETCH_POINTS, PercentileEngine, PercentileMethod, SortedPercentiles,
+    };
+    use crate::synthetic::SeededRng;
+
+    fn values(seed: u64, n: usize) -> Vec<f64> {
+        let mut rng = SeededRng::new(seed);
+        (0..n).map(|_| rng.normal(400.0, 80.0)).collect()
+    }
+
+    #[test]
+    fn digest_sketch_matches_exact_percentiles() {
+        let data = values(11, 50_000);
+        let mut digest = TDigest::default();
+        for value in &data {
+            digest.add(*value);
+        }
+        assert_eq!(digest.count(), 50_000);
+        assert!(digest.centroids.len() < 200);
+
+        let exact = SortedPercentiles::new(data);
+        let sketch = digest.to_sketch(DEFAULT_SKETCH_POINTS);
+        for value in [200.0, 300.0, 400.0, 500.0, 600.0] {
+            let want = exact
+                .percentile_with(value, PercentileMethod::Interpolated)
+                .unwrap();
+            let got = sketch
+                .percentile_with(value, PercentileMethod::Interpolated)
+                .unwrap();
+            assert!(
+                (want - got).abs() < 0.5,
+                "{value}: exact {want}, digest {got}"
+            );
+        }
+    }
+
+    #[test]
+    fn merged_digests_match_single_pass() {
+        let data = values(5, 20_000);
+        let mut whole = TDigest::default();
+        let mut left = TDigest::default();
+        let mut right = TDigest::default();
+        for (i, value) in data.iter().enumerate() {
+            whole.add(*value);
+            if i % 2 == 0 {
+                left.add(*value);
+            } else {
+                right.add(*value);
+            }
+        }
+        left.merge(&right);
+
+        assert_eq!(left.count(), whole.count());
+        for q in [0.01, 0.25, 0.5, 0.75, 0.99] {
+            let a = left.quantile(q).unwrap();
+            let b = whole.quantile(q).unwrap();
+            assert!((a - b).abs() < 3.0, "q={q}: merged {a}, single {b}");
+        }
+    }
+
+    #[test]
+    fn empty_and_extreme_quantiles() {
+        let mut digest = TDigest::default();
+        assert_eq!(digest.quantile(0.5), None);
+        digest.add(f64::NAN);
+        assert_eq!(digest.count(), 0);
+
+        for value in [3.0, 1.0, 2.0] {
+            digest.add(value);
+        }
+        assert_eq!(digest.quantile(0.0), Some(1.0));
+        assert_eq!(digest.quantile(1.0), Some(3.0));
+        assert_eq!(digest.quantile(0.5), Some(2.0));
+    }
+}
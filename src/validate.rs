@@ -0,0 +1,181 @@
+use crate::import::{ImportReport, ImportedLifter, RowError};
+use crate::scoring::dots;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Largest accepted difference between a dataset's DOTS column and the recomputed value.
+pub const DOTS_TOLERANCE: f64 = 0.5;
+
+/// A row whose reported DOTS disagrees with the value recomputed from its total.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DotsMismatch {
+    pub name: String,
+    pub reported: f64,
+    pub recomputed: f64,
+}
+
+/// Integrity summary for an imported dataset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationReport {
+    pub accepted: usize,
+    pub rejected: Vec<RowError>,
+    /// Share of accepted rows missing each optional lift, 0-1.
+    pub null_rates: Vec<(&'static str, f64)>,
+    /// Identical records and how many times each appears.
+    pub duplicates: Vec<(String, usize)>,
+    pub dots_mismatches: Vec<DotsMismatch>,
+}
+
+impl ValidationReport {
+    /// Rejected rows, duplicates, and DOTS mismatches count as failures; null rates do not.
+    pub fn is_ok(&self) -> bool {
+        self.rejected.is_empty() && self.duplicates.is_empty() && self.dots_mismatches.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "rows: {} accepted, {} rejected",
+            self.accepted,
+            self.rejected.len()
+        )?;
+        for error in &self.rejected {
+            writeln!(f, "  rejected {error}")?;
+        }
+        for (column, rate) in &self.null_rates {
+            writeln!(f, "null rate {column}: {:.1}%", rate * 100.0)?;
+        }
+        for (record, count) in &self.duplicates {
+            writeln!(f, "duplicate x{count}: {record}")?;
+        }
+        for mismatch in &self.dots_mismatches {
+            writeln!(
+                f,
+                "DOTS mismatch for {}: reported {:.2}, recomputed {:.2}",
+                mismatch.name, mismatch.reported, mismatch.recomputed
+            )?;
+        }
+        write!(f, "result: {}", if self.is_ok() { "OK" } else { "FAILED" })
+    }
+}
+
+fn record_key(lifter: &ImportedLifter) -> String {
+    let weight = |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{v:.2}"));
+    format!(
+        "{} ({}, {:.2} kg bw, S {} / B {} / D {} / T {})",
+        lifter.name,
+        lifter.sex,
+        lifter.bodyweight_kg,
+        weight(lifter.squat_kg),
+        weight(lifter.bench_kg),
+        weight(lifter.deadlift_kg),
+        weight(lifter.total_kg),
+    )
+}
+
+/// Checks an import for null rates, duplicate records, and DOTS recomputation mismatches.
+///
+/// Impossible values are already rejected row by row during import and are carried over.
+pub fn validate_import(report: &ImportReport) -> ValidationReport {
+    let lifters = &report.lifters;
+    let null_rate = |missing: usize| {
+        if lifters.is_empty() {
+            0.0
+        } else {
+            missing as f64 / lifters.len() as f64
+        }
+    };
+    let count_missing = |get: fn(&ImportedLifter) -> Option<f64>| {
+        lifters.iter().filter(|l| get(l).is_none()).count()
+    };
+    let null_rates = vec![
+        ("squat", null_rate(count_missing(|l| l.squat_kg))),
+        ("bench", null_rate(count_missing(|l| l.bench_kg))),
+        ("deadlift", null_rate(count_missing(|l| l.deadlift_kg))),
+        ("total", null_rate(count_missing(|l| l.total_kg))),
+    ];
+
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for lifter in lifters {
+        *seen.entry(record_key(lifter)).or_default() += 1;
+    }
+    let mut duplicates: Vec<(String, usize)> =
+        seen.into_iter().filter(|(_, count)| *count > 1).collect();
+    duplicates.sort();
+
+    let dots_mismatches = lifters
+        .iter()
+        .filter_map(|lifter| {
+            let reported = lifter.reported_dots?;
+            let recomputed = dots(lifter.total_kg?, lifter.bodyweight_kg, lifter.sex);
+            ((reported - recomputed).abs() > DOTS_TOLERANCE).then(|| DotsMismatch {
+                name: lifter.name.clone(),
+                reported,
+                recomputed,
+            })
+        })
+        .collect();
+
+    ValidationReport {
+        accepted: lifters.len(),
+        rejected: report.rejected.clone(),
+        null_rates,
+        duplicates,
+        dots_mismatches,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::validate_import;
+    use crate::import::{ColumnMapping, WeightUnit, import_csv};
+
+    fn mapping() -> ColumnMapping {
+        ColumnMapping {
+            name: "Name".to_string(),
+            sex: "Sex".to_string(),
+            bodyweight: "BodyweightKg".to_string(),
+            squat: Some("Best3SquatKg".to_string()),
+            bench: Some("Best3BenchKg".to_string()),
+            deadlift: Some("Best3DeadliftKg".to_string()),
+            total: Some("TotalKg".to_string()),
+            dots: Some("Dots".to_string()),
+            equipment: None,
+            unit: WeightUnit::Kg,
+        }
+    }
+
+    const HEADER: &str =
+        "Name,Sex,BodyweightKg,Best3SquatKg,Best3BenchKg,Best3DeadliftKg,TotalKg,Dots";
+
+    #[test]
+    fn clean_dataset_passes() {
+        let csv = format!("{HEADER}\nA,M,93,250,170,280,700,445.38\nB,F,63,,80,,,\n");
+        let report = validate_import(&import_csv(&csv, &mapping()).unwrap());
+
+        assert!(report.is_ok(), "{report}");
+        assert_eq!(report.accepted, 2);
+        assert_eq!(report.null_rates[0], ("squat", 0.5));
+    }
+
+    #[test]
+    fn flags_duplicates_mismatches_and_impossible_values() {
+        let csv = format!(
+            "{HEADER}\n\
+             A,M,93,250,170,280,700,445.38\n\
+             A,M,93,250,170,280,700,445.38\n\
+             C,M,93,250,170,280,700,500\n\
+             D,M,-5,250,170,280,700,\n"
+        );
+        let report = validate_import(&import_csv(&csv, &mapping()).unwrap());
+
+        assert!(!report.is_ok());
+        assert_eq!(report.rejected.len(), 1);
+        assert_eq!(report.duplicates.len(), 1);
+        assert_eq!(report.duplicates[0].1, 2);
+        assert_eq!(report.dots_mismatches.len(), 1);
+        assert_eq!(report.dots_mismatches[0].name, "C");
+    }
+}